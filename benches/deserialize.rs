@@ -0,0 +1,116 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde_json::json;
+
+use yahoo_finance_api as yahoo;
+
+/// Body of a chart response with the given number of 1 minute bars
+fn chart_body(bars: usize) -> Vec<u8> {
+    let start = 1_577_975_400i64;
+    let timestamps: Vec<i64> = (0..bars as i64).map(|i| start + 60 * i).collect();
+    let prices: Vec<f64> = (0..bars).map(|i| 100.0 + (i % 500) as f64 * 0.01).collect();
+    let volumes: Vec<u64> = (0..bars as u64).map(|i| 1000 + i % 7919).collect();
+    let body = json!({
+        "chart": {
+            "result": [{
+                "meta": {
+                    "currency": "USD",
+                    "symbol": "AAPL",
+                    "exchangeName": "NMS",
+                    "instrumentType": "EQUITY",
+                    "firstTradeDate": 345479400,
+                    "regularMarketTime": start + 60 * bars as i64,
+                    "gmtoffset": -18000,
+                    "timezone": "EST",
+                    "exchangeTimezoneName": "America/New_York",
+                    "regularMarketPrice": 101.23,
+                    "chartPreviousClose": 100.0,
+                    "priceHint": 2,
+                    "currentTradingPeriod": {
+                        "pre": { "timezone": "EST", "start": start - 19800, "end": start, "gmtoffset": -18000 },
+                        "regular": { "timezone": "EST", "start": start, "end": start + 23400, "gmtoffset": -18000 },
+                        "post": { "timezone": "EST", "start": start + 23400, "end": start + 37800, "gmtoffset": -18000 }
+                    },
+                    "dataGranularity": "1m",
+                    "range": "",
+                    "validRanges": ["1d", "5d", "1mo"]
+                },
+                "timestamp": timestamps,
+                "indicators": {
+                    "quote": [{
+                        "volume": volumes,
+                        "high": prices,
+                        "close": prices,
+                        "low": prices,
+                        "open": prices
+                    }]
+                }
+            }],
+            "error": null
+        }
+    });
+    serde_json::to_vec(&body).unwrap()
+}
+
+/// Body of a timeseries response with the given number of quarterly reports per type
+fn timeseries_body(reports: usize) -> Vec<u8> {
+    let result: Vec<_> = ["quarterlyTotalDebt", "quarterlyCurrentDebt", "quarterlyCashAndCashEquivalents"]
+        .iter()
+        .map(|key| {
+            let values: Vec<_> = (0..reports)
+                .map(|i| {
+                    json!({
+                        "asOfDate": format!("{}-{:02}-30", 1990 + i / 4, 3 * (i % 4) + 3),
+                        "currencyCode": "USD",
+                        "periodType": "3M",
+                        "reportedValue": { "raw": 1.0e9 + i as f64, "fmt": "1.00B" }
+                    })
+                })
+                .collect();
+            json!({ "meta": { "symbol": ["AAPL"], "type": [key] }, key.to_string(): values })
+        })
+        .collect();
+    serde_json::to_vec(&json!({ "timeseries": { "result": result, "error": null } })).unwrap()
+}
+
+fn chart(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chart");
+    for bars in [1_000, 100_000] {
+        let body = chart_body(bars);
+        group.throughput(Throughput::Bytes(body.len() as u64));
+        group.bench_with_input(BenchmarkId::new("via_value", bars), &body, |b, body| {
+            b.iter(|| {
+                let json = serde_json::from_slice(black_box(body)).unwrap();
+                yahoo::YResponse::from_json(json).unwrap()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("from_slice", bars), &body, |b, body| {
+            b.iter(|| yahoo::YResponse::from_slice(black_box(body)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn timeseries(c: &mut Criterion) {
+    let mut group = c.benchmark_group("timeseries");
+    let body = timeseries_body(120);
+    group.throughput(Throughput::Bytes(body.len() as u64));
+    group.bench_function("via_value", |b| {
+        b.iter(|| {
+            let json = serde_json::from_slice(black_box(&body)).unwrap();
+            yahoo::YFinancialsResponse::from_timeseries(
+                yahoo::YTimeseriesResponse::from_json(json).unwrap(),
+            )
+        })
+    });
+    group.bench_function("from_slice", |b| {
+        b.iter(|| {
+            yahoo::YFinancialsResponse::from_timeseries(
+                yahoo::YTimeseriesResponse::from_slice(black_box(&body)).unwrap(),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, chart, timeseries);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+use std::io;
+
+use time::macros::datetime;
+
+use yahoo_finance_api as yahoo;
+
+#[cfg(not(feature = "blocking"))]
+#[tokio::main]
+async fn main() {
+    let conn   = yahoo::YahooConnector::new();
+    let ticker = "OKE";
+    let start  = datetime!(2020-07-25 00:00:00 UTC);
+    let end    = datetime!(2020-11-01 00:00:00 UTC);
+
+    let hist  = conn.get_quote_history(ticker, start, end).await.unwrap();
+
+    // semicolon separated quotes with dates in the local time of the exchange
+    let options = yahoo::CsvOptions {
+        columns: vec![yahoo::CsvColumn::Date, yahoo::CsvColumn::Open, yahoo::CsvColumn::Close],
+        date_format: yahoo::CsvDateFormat::ExchangeLocal,
+        delimiter: b';',
+    };
+    hist.write_quotes_csv(io::stdout(), &options).unwrap();
+    println!();
+    // dividends have fixed columns, so only the date format and delimiter apply
+    hist.write_dividends_csv(io::stdout(), &yahoo::CsvEventOptions::from(&options)).unwrap();
+}
+
+#[cfg(feature = "blocking")]
+fn main() {
+    let conn   = yahoo::YahooConnector::new();
+    let ticker = "OKE";
+    let start  = datetime!(2020-07-25 00:00:00 UTC);
+    let end    = datetime!(2020-11-01 00:00:00 UTC);
+
+    let hist  = conn.get_quote_history(ticker, start, end).unwrap();
+
+    hist.write_quotes_csv(io::stdout(), &yahoo::CsvOptions::default()).unwrap();
+}
@@ -1,4 +1,4 @@
-use time::{OffsetDateTime, macros::datetime};
+use time::macros::datetime;
 
 use yahoo_finance_api as yahoo;
 
@@ -15,15 +15,13 @@ async fn main() {
     println!("{}", ticker);
     println!("QUOTES");
     for quote in hist.quotes().unwrap() {
-        let time = OffsetDateTime::from_unix_timestamp(quote.timestamp as i64).unwrap();
-        println!("{} | {:.2} | {:.2}", time, quote.open, quote.close);
+        println!("{} | {:.2} | {:.2}", quote.datetime(), quote.open, quote.close);
     }
 
     // Display dividends paid during the requested period
     println!("DIVIDENDS");
     for dividend in hist.dividends().unwrap() {
-        let date = OffsetDateTime::from_unix_timestamp(dividend.date as i64).unwrap();
-        println!("{} | {:.3}", date, dividend.amount);
+        println!("{} | {:.3}", dividend.datetime(), dividend.amount);
     }
 }
 
@@ -1,4 +1,4 @@
-use time::{OffsetDateTime, macros::datetime};
+use time::macros::datetime;
 
 use yahoo_finance_api as yahoo;
 
@@ -15,15 +15,13 @@ async fn main() {
     println!("{}", ticker);
     println!("QUOTES");
     for quote in hist.quotes().unwrap() {
-        let time = OffsetDateTime::from_unix_timestamp(quote.timestamp as i64).unwrap();
-        println!("{} | {:.2} | {:.2}", time, quote.open, quote.close);
+        println!("{} | {:.2} | {:.2}", quote.datetime(), quote.open, quote.close);
     }
 
     // Get any splits that occured during the requested period
     println!("SPLITS");
     for split in hist.splits().unwrap() {
-        let date = OffsetDateTime::from_unix_timestamp(split.date as i64).unwrap();
-        println!("{} | {} : {}", date, split.numerator, split.denominator);
+        println!("{} | {} : {}", split.datetime(), split.numerator, split.denominator);
     }
 }
 
@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+
+use super::YahooError;
+use crate::quotes::{Dividend, QuoteOpt, Split, YQuoteBlock, YResponse};
+
+/// Corporate actions to adjust historic quotes for
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjustmentMode {
+    /// Adjust for splits only. Yahoo's prices and volumes are already adjusted for
+    /// splits, so the quotes are left as they are.
+    Splits,
+    /// Adjust for splits and the prices for dividends as well, like Yahoo's `adjclose`
+    SplitsAndDividends,
+}
+
+/// Multiplier to back-adjust the prices of a period for the corporate actions after it
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct AdjustmentFactor {
+    /// Unix timestamp (seconds since the epoch, UTC) of the start of the period
+    pub timestamp: i64,
+    /// Factor for open, high, low and close
+    pub price: f64,
+}
+
+/// Number of shares after the split per share before it, `None` for invalid splits
+pub(crate) fn split_ratio(split: &Split) -> Option<f64> {
+    if split.numerator == 0 || split.denominator == 0 {
+        return None;
+    }
+    Some(split.numerator as f64 / split.denominator as f64)
+}
+
+/// Price factor `1 - amount / close` of a distribution (e.g. a dividend) with the given
+/// ex-date, using the last known close before it. `None` without a preceding close or
+/// if the amount exceeds it.
+pub(crate) fn distribution_factor(timestamps: &[i64], close: &[Option<f64>], date: i64, amount: f64) -> Option<f64> {
+    let i = timestamps.partition_point(|t| *t < date);
+    close[..i]
+        .iter()
+        .rev()
+        .find_map(|close| *close)
+        .map(|close| 1.0 - amount / close)
+        .filter(|factor| *factor > 0.0)
+}
+
+/// Back-adjustment factors of quotes with the given timestamps (in ascending order) and
+/// closing prices, which are expected to be adjusted for splits already. A dividend dated
+/// `t` applies to all quotes before `t` with the factor given by [`distribution_factor`];
+/// it is expected in the same terms as the prices.
+fn adjustment_factors(
+    timestamps: &[i64],
+    close: &[Option<f64>],
+    dividends: &[Dividend],
+    mode: AdjustmentMode,
+) -> Vec<AdjustmentFactor> {
+    let dividends = match mode {
+        AdjustmentMode::Splits => &[],
+        AdjustmentMode::SplitsAndDividends => dividends,
+    };
+    // factors of the dividends going ex right after each quote
+    let mut price = vec![1.0; timestamps.len()];
+    for dividend in dividends {
+        // a factor implies a preceding quote
+        if let Some(factor) = distribution_factor(timestamps, close, dividend.date, dividend.amount) {
+            let i = timestamps.partition_point(|t| *t < dividend.date);
+            price[i - 1] *= factor;
+        }
+    }
+
+    let mut cumulative = 1.0;
+    let mut factors: Vec<AdjustmentFactor> = timestamps
+        .iter()
+        .zip(price)
+        .rev()
+        .map(|(timestamp, price)| {
+            cumulative *= price;
+            AdjustmentFactor {
+                timestamp: *timestamp,
+                price: cumulative,
+            }
+        })
+        .collect();
+    factors.reverse();
+    factors
+}
+
+impl YResponse {
+    /// Back-adjustment factors of the quotes, see [`YQuoteBlock::adjustment_factors`]
+    pub fn adjustment_factors(&self, mode: AdjustmentMode) -> Result<Vec<AdjustmentFactor>, YahooError> {
+        self.first_result()?.adjustment_factors(mode)
+    }
+
+    /// Quotes back-adjusted for corporate actions, see [`YQuoteBlock::adjusted_quotes`]
+    pub fn adjusted_quotes(&self, mode: AdjustmentMode) -> Result<Vec<QuoteOpt>, YahooError> {
+        self.first_result()?.adjusted_quotes(mode)
+    }
+
+    /// Largest relative deviation of the adjusted close from Yahoo's `adjclose`,
+    /// see [`YQuoteBlock::adjclose_deviation`]
+    pub fn adjclose_deviation(&self) -> Result<Option<f64>, YahooError> {
+        self.first_result()?.adjclose_deviation()
+    }
+}
+
+impl YQuoteBlock {
+    /// Back-adjustment factors of the prices for the corporate actions of the given mode
+    /// contained in the block. Yahoo's open, high, low, close and volume as well as the
+    /// dividend amounts are already adjusted for splits, so only dividends result in
+    /// factors other than 1.
+    pub fn adjustment_factors(&self, mode: AdjustmentMode) -> Result<Vec<AdjustmentFactor>, YahooError> {
+        let series = self.quote_series()?;
+        Ok(adjustment_factors(series.timestamps(), series.close(), &self.dividends()?, mode))
+    }
+
+    /// Quotes with open, high, low and close back-adjusted for corporate actions, i.e. in
+    /// terms of the latest quote. The volume is left as is, since it is adjusted for splits
+    /// by Yahoo. Missing values remain `None`; `adjclose` is Yahoo's adjusted close, if
+    /// contained in the block.
+    pub fn adjusted_quotes(&self, mode: AdjustmentMode) -> Result<Vec<QuoteOpt>, YahooError> {
+        let factors = self.adjustment_factors(mode)?;
+        Ok(self
+            .quote_series()?
+            .iter()
+            .zip(factors)
+            .map(|(quote, factor)| QuoteOpt {
+                open: quote.open.map(|open| open * factor.price),
+                high: quote.high.map(|high| high * factor.price),
+                low: quote.low.map(|low| low * factor.price),
+                close: quote.close.map(|close| close * factor.price),
+                ..quote
+            })
+            .collect())
+    }
+
+    /// Largest relative deviation of the close adjusted for dividends from Yahoo's
+    /// `adjclose`, to verify the adjustment. Yahoo's `adjclose` also accounts for the
+    /// dividends after the last quote, so both are compared relative to their ratio at
+    /// the last quote with both values. `None` if the block contains no adjusted close
+    /// prices.
+    pub fn adjclose_deviation(&self) -> Result<Option<f64>, YahooError> {
+        let quotes = self.adjusted_quotes(AdjustmentMode::SplitsAndDividends)?;
+        if self.indicators.adjclose.is_none() {
+            return Ok(None);
+        }
+        let ratios: Vec<f64> = quotes
+            .iter()
+            .filter_map(|quote| match (quote.close, quote.adjclose) {
+                (Some(close), Some(adjclose)) if adjclose != 0.0 => Some(close / adjclose),
+                _ => None,
+            })
+            .collect();
+        let last = match ratios.last() {
+            Some(last) => *last,
+            None => return Ok(Some(0.0)),
+        };
+        Ok(Some(
+            ratios
+                .iter()
+                .map(|ratio| (ratio / last - 1.0).abs())
+                .fold(0.0, f64::max),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quotes::MissingValuePolicy;
+    use crate::quotes::tests::{chart_json_without_adjclose, split_chart_json};
+
+    #[test]
+    fn test_adjusted_quotes() {
+        let response = YResponse::from_json(split_chart_json()).unwrap();
+
+        let factors = response.adjustment_factors(AdjustmentMode::SplitsAndDividends).unwrap();
+        assert_eq!(factors[0].price, 1.0 - 0.205 / 113.9025);
+        assert!(factors[1..].iter().all(|factor| factor.price == 1.0));
+
+        let quotes = response.adjusted_quotes(AdjustmentMode::SplitsAndDividends).unwrap();
+        assert_eq!(quotes[0].close, Some(113.9025 * factors[0].price));
+        assert_eq!(quotes[0].volume, Some(202428800));
+        // the prices are continuous across the split, which yahoo! already adjusted for
+        assert_eq!(quotes[2].close, Some(124.8075));
+        assert_eq!(quotes[3].open, Some(127.58));
+        assert_eq!(quotes[3].volume, Some(225702700));
+
+        // yahoo!'s quotes are already adjusted for splits
+        let quotes = response.adjusted_quotes(AdjustmentMode::Splits).unwrap();
+        let unadjusted = response.quotes_opt(MissingValuePolicy::Keep).unwrap();
+        assert_eq!(quotes, unadjusted);
+    }
+
+    #[test]
+    fn test_adjclose_deviation() {
+        let response = YResponse::from_json(split_chart_json()).unwrap();
+        assert!(response.adjclose_deviation().unwrap().unwrap() < 1e-5);
+
+        let response = YResponse::from_json(chart_json_without_adjclose()).unwrap();
+        assert_eq!(response.adjclose_deviation().unwrap(), None);
+    }
+}
@@ -0,0 +1,183 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, DictionaryArray, Float64Array, TimestampSecondArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Int32Type, Schema, SchemaRef, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+
+use super::YahooError;
+use crate::quotes::{MissingValuePolicy, QuoteOpt, YResponse};
+
+/// Schema of record batches of quotes: a dictionary encoded `symbol`, a `timestamp` in
+/// seconds (UTC), the prices `open`, `high`, `low`, `close` and `adjclose` and the `volume`.
+/// Prices and volume are nullable, e.g. `adjclose` is null for intraday quotes.
+pub fn quote_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new(
+            "symbol",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            false,
+        ),
+        Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Second, Some("UTC".into())),
+            false,
+        ),
+        Field::new("open", DataType::Float64, true),
+        Field::new("high", DataType::Float64, true),
+        Field::new("low", DataType::Float64, true),
+        Field::new("close", DataType::Float64, true),
+        Field::new("adjclose", DataType::Float64, true),
+        Field::new("volume", DataType::UInt64, true),
+    ]))
+}
+
+/// Convert the quote histories of one or more symbols to a single record batch with the
+/// schema given by [`quote_schema`], e.g. as returned by
+/// [`quotes_opt`](crate::YResponse::quotes_opt). Missing values are null.
+pub fn quotes_to_record_batch<'a, I>(histories: I) -> Result<RecordBatch, YahooError>
+where
+    I: IntoIterator<Item = (&'a str, &'a [QuoteOpt])>,
+{
+    let mut symbols = Vec::new();
+    let mut quotes = Vec::new();
+    for (symbol, history) in histories {
+        symbols.extend(std::iter::repeat_n(symbol, history.len()));
+        quotes.extend(history);
+    }
+    let float_column = |value: fn(&QuoteOpt) -> Option<f64>| -> ArrayRef {
+        Arc::new(quotes.iter().map(value).collect::<Float64Array>())
+    };
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(symbols.into_iter().collect::<DictionaryArray<Int32Type>>()),
+        Arc::new(
+            TimestampSecondArray::from(quotes.iter().map(|quote| quote.timestamp).collect::<Vec<i64>>())
+                .with_timezone("UTC"),
+        ),
+        float_column(|quote| quote.open),
+        float_column(|quote| quote.high),
+        float_column(|quote| quote.low),
+        float_column(|quote| quote.close),
+        float_column(|quote| quote.adjclose),
+        Arc::new(quotes.iter().map(|quote| quote.volume).collect::<UInt64Array>()),
+    ];
+    RecordBatch::try_new(quote_schema(), columns).map_err(arrow_failed)
+}
+
+/// Write the quote histories of one or more symbols as Parquet files partitioned by symbol,
+/// i.e. to `<dir>/symbol=<symbol>/quotes.parquet`. The `symbol` column is omitted from the
+/// files, since it is given by the partition. Returns the paths of the written files.
+pub fn write_parquet_by_symbol<'a, I>(dir: impl AsRef<Path>, histories: I) -> Result<Vec<PathBuf>, YahooError>
+where
+    I: IntoIterator<Item = (&'a str, &'a [QuoteOpt])>,
+{
+    let mut paths = Vec::new();
+    for (symbol, quotes) in histories {
+        let batch = quotes_to_record_batch([(symbol, quotes)])?;
+        // drop the symbol column
+        let batch = batch
+            .project(&(1..batch.num_columns()).collect::<Vec<usize>>())
+            .map_err(arrow_failed)?;
+
+        let partition = dir.as_ref().join(format!("symbol={}", partition_name(symbol)));
+        fs::create_dir_all(&partition).map_err(arrow_failed)?;
+        let path = partition.join("quotes.parquet");
+        let file = File::create(&path).map_err(arrow_failed)?;
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), None).map_err(arrow_failed)?;
+        writer.write(&batch).map_err(arrow_failed)?;
+        writer.close().map_err(arrow_failed)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+impl YResponse {
+    /// Convert all quotes to a record batch with the schema given by [`quote_schema`],
+    /// keeping missing values as null
+    pub fn to_record_batch(&self) -> Result<RecordBatch, YahooError> {
+        let quotes = self.quotes_opt(MissingValuePolicy::Keep)?;
+        let symbol = &self.first_result()?.meta.symbol;
+        quotes_to_record_batch([(symbol.as_str(), quotes.as_slice())])
+    }
+}
+
+/// Escape characters of the symbol which are not safe in directory names,
+/// e.g. `/`, as `%XX` like Hive does
+fn partition_name(symbol: &str) -> String {
+    symbol
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' | '^' => c.to_string(),
+            _ => c
+                .to_string()
+                .bytes()
+                .map(|b| format!("%{:02X}", b))
+                .collect(),
+        })
+        .collect()
+}
+
+fn arrow_failed<E>(source: E) -> YahooError
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    YahooError::ArrowFailed {
+        source: source.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::TimestampSecondType;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+    use crate::quotes::tests::{chart_json, chart_json_without_adjclose};
+
+    #[test]
+    fn test_record_batch() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let quotes = response.quotes_opt(MissingValuePolicy::Keep).unwrap();
+        let batch = quotes_to_record_batch([("IBM", quotes.as_slice()), ("IBM2", &quotes[..1])]).unwrap();
+        assert_eq!(batch.schema(), quote_schema());
+        assert_eq!(batch.num_rows(), 4);
+
+        let symbols = batch.column(0).as_dictionary::<Int32Type>();
+        assert_eq!(symbols.values().len(), 2);
+        let timestamps = batch.column(1).as_primitive::<TimestampSecondType>();
+        assert_eq!(timestamps.value(0), 1593610200);
+        assert_eq!(batch.column(5).as_primitive::<arrow::datatypes::Float64Type>().value(3), 119.7);
+        // missing values are null rather than zero
+        assert!(batch.column(4).is_null(1));
+        assert!(batch.column(7).is_null(1));
+        assert_eq!(batch.column(4).null_count(), 1);
+
+        assert_eq!(response.to_record_batch().unwrap().num_rows(), 3);
+
+        // intraday quotes come without adjusted close
+        let batch = YResponse::from_json(chart_json_without_adjclose()).unwrap().to_record_batch().unwrap();
+        assert_eq!(batch.column(6).null_count(), 3);
+        assert!(batch.column(5).is_valid(0));
+    }
+
+    #[test]
+    fn test_write_parquet_by_symbol() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let quotes = response.quotes_opt(MissingValuePolicy::Keep).unwrap();
+        let dir = std::env::temp_dir().join(format!("yahoo_finance_api_parquet_{}", std::process::id()));
+        let paths = write_parquet_by_symbol(&dir, [("IBM", quotes.as_slice()), ("BRK/B", &quotes[..1])]).unwrap();
+        assert_eq!(paths[0], dir.join("symbol=IBM").join("quotes.parquet"));
+        assert_eq!(paths[1], dir.join("symbol=BRK%2FB").join("quotes.parquet"));
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&paths[0]).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+        assert_eq!(rows, 3);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
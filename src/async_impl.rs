@@ -1,10 +1,18 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
+use reqwest::header::RETRY_AFTER;
 use reqwest::Url;
+use serde::{Deserialize, Serialize};
 use time::{Date, OffsetDateTime, UtcOffset};
 use async_compat::CompatExt;
 
-use crate::financials::ReportedValue;
+use crate::esg::YEsgData;
+use crate::financials::YTimeseriesResponse;
+use crate::quote_summary::YQuoteSummaryResponse;
+use crate::sec_filings::YSecFilingsData;
+use crate::statistics::YQuoteSummaryStore;
+use crate::utils::datetime_from_timestamp;
 
 use super::*;
 
@@ -43,7 +51,7 @@ impl YahooConnector {
             interval = interval,
             range = range
         );
-        YResponse::from_json(send_request(&url).await?)
+        send_request(&url, Some(ticker), YResponse::from_slice).await
     }
     /// Retrieve the quote history for the given ticker form date start to end (inclusive), if available; specifying the interval of the ticker.
     pub async fn get_quote_history_interval(
@@ -52,31 +60,164 @@ impl YahooConnector {
         start: OffsetDateTime,
         end: OffsetDateTime,
         interval: &str,
+    ) -> Result<YResponse, YahooError> {
+        let url = self.chart_period_url(ticker, start, end, interval);
+        send_request(&url, Some(ticker), YResponse::from_slice).await
+    }
+
+    /// Retrieve the quote history for the given ticker form date start to end (inclusive) with the
+    /// given interval, together with the prices of the given symbols for comparison, available via
+    /// [`YQuoteBlock::comparison`]
+    pub async fn get_quote_history_with_comparisons(
+        &self,
+        ticker: &str,
+        comparisons: &[&str],
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        interval: &str,
     ) -> Result<YResponse, YahooError> {
         let url = format!(
-            YCHART_PERIOD_QUERY!(),
-            url = self.url,
-            symbol = ticker,
-            start = start.to_offset(UtcOffset::UTC).unix_timestamp(),
-            end = end.to_offset(UtcOffset::UTC).unix_timestamp(),
-            interval = interval
+            "{}&comparisons={}",
+            self.chart_period_url(ticker, start, end, interval),
+            comparisons.join(",")
         );
-        YResponse::from_json(send_request(&url).await?)
+        send_request(&url, Some(ticker), YResponse::from_slice).await
     }
 
+    /// Retrieve the quote history for the given ticker form date start to end (inclusive) with the
+    /// given interval, with the prices parsed exactly as decimals
+    pub async fn get_decimal_quote_history(
+        &self,
+        ticker: &str,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        interval: &str,
+    ) -> Result<Vec<DecimalQuote>, YahooError> {
+        let url = self.chart_period_url(ticker, start, end, interval);
+        send_request(&url, Some(ticker), |body| {
+            YDecimalResponse::from_slice(body)?.decimal_quotes()
+        })
+        .await
+    }
+
+    /// Retrieve the splits, dividends and capital gains of the given ticker from date start
+    /// to end (inclusive) as a chronologically ordered ledger. The actions are retrieved with
+    /// quarterly quotes. If there are dividends or capital gains, the daily quote history from
+    /// two weeks before the first to the last of them is retrieved in a second request for
+    /// their adjustment factors, which is about as costly as [`get_quote_history`](Self::get_quote_history).
+    pub async fn get_corporate_actions(
+        &self,
+        ticker: &str,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> Result<Vec<CorporateAction>, YahooError> {
+        let events = self.get_quote_history_interval(ticker, start, end, "3mo").await?;
+        let block = events.first_result()?;
+        let dates: Vec<i64> = block
+            .dividends()?
+            .iter()
+            .map(|dividend| dividend.date)
+            .chain(block.capital_gains()?.iter().map(|gain| gain.date))
+            .collect();
+        let (first, last) = match (dates.iter().min(), dates.iter().max()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return block.corporate_actions_with_closes(&[], &[]),
+        };
+        let url = self.chart_period_url(
+            ticker,
+            datetime_from_timestamp(first) - time::Duration::days(14),
+            datetime_from_timestamp(last),
+            "1d",
+        );
+        send_request(&url, Some(ticker), |body| {
+            let daily = YResponse::from_slice(body)?;
+            let series = daily.quote_series()?;
+            block.corporate_actions_with_closes(series.timestamps(), series.close())
+        })
+        .await
+    }
+
+    /// Retrieve the annual basic average shares for the given ticker from the
+    /// fundamentals timeseries API, falling back to scraping the financials page
+    /// if the API rejects the request, fails or returns unexpected data
     pub async fn get_financials(
         &self,
         ticker: &str
+    ) -> Result<YFinancialsResponse, YahooError> {
+        let types = ["annualBasicAverageShares".to_string()];
+        match self.get_timeseries(ticker, &types).await {
+            Ok(response) => Ok(YFinancialsResponse::from_timeseries(response)),
+            Err(err) if scraping_may_help(&err) => self.scrape_financials(ticker).await.map_err(|_| err),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Retrieve the key statistics and financial data for the given ticker from the
+    /// quote summary API, falling back to scraping the statistics page if the API
+    /// rejects the request, fails or returns unexpected data
+    pub async fn get_statistics(
+        &self,
+        ticker: &str
+    ) -> Result<YStatisticsResponse, YahooError> {
+        match self
+            .get_quote_summary::<YQuoteSummaryStore>(ticker, "defaultKeyStatistics,financialData")
+            .await
+        {
+            Ok(store) => Ok(YStatisticsResponse {
+                quote_summary_store: Some(store),
+            }),
+            Err(err) if scraping_may_help(&err) => self.scrape_statistics(ticker).await.map_err(|_| err),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Retrieve the profile, top holdings and performance of a fund (e.g. an ETF or mutual fund)
+    pub async fn get_fund_data(&self, ticker: &str) -> Result<YFundData, YahooError> {
+        self.get_quote_summary(
+            ticker,
+            "fundProfile,topHoldings,fundPerformance,defaultKeyStatistics",
+        )
+        .await
+    }
+
+    /// Retrieve the ESG risk scores of the given ticker, or `None` if yahoo! has no ESG rating for it
+    pub async fn get_esg_scores(&self, ticker: &str) -> Result<Option<EsgScores>, YahooError> {
+        let data = self
+            .get_optional_quote_summary::<YEsgData>(ticker, "esgScores")
+            .await?;
+        Ok(data.and_then(|data| data.esg_scores))
+    }
+
+    /// Retrieve the filings of the given ticker submitted to the SEC, most recent first
+    pub async fn get_sec_filings(&self, ticker: &str) -> Result<Vec<SecFiling>, YahooError> {
+        let data = self
+            .get_optional_quote_summary::<YSecFilingsData>(ticker, "secFilings")
+            .await?;
+        let mut filings = data
+            .and_then(|data| data.sec_filings)
+            .map(|sec_filings| sec_filings.filings)
+            .unwrap_or_default();
+        filings.sort_by_key(|f| std::cmp::Reverse(f.date));
+        Ok(filings)
+    }
+
+    /// Retrieve the financials by scraping the data embedded in the financials page.
+    /// This is only a fallback, as yahoo! does not reliably ship this data anymore.
+    pub async fn scrape_financials(
+        &self,
+        ticker: &str
     ) -> Result<YFinancialsResponse, YahooError> {
         let url = format!(
             YFINANCIALS_QUERY!(),
             url = YSCRAPE_URL,
             symbol = ticker
         );
-        YFinancialsResponse::from_json(send_scrape_request(&url).await?)
+        send_scrape_request(&url, ticker, YFinancialsResponse::from_json).await
     }
 
-    pub async fn get_statistics(
+    /// Retrieve the statistics by scraping the data embedded in the statistics page.
+    /// This is only a fallback, as yahoo! does not reliably ship this data anymore.
+    pub async fn scrape_statistics(
         &self,
         ticker: &str
     ) -> Result<YStatisticsResponse, YahooError> {
@@ -85,13 +226,62 @@ impl YahooConnector {
             url = YSCRAPE_URL,
             symbol = ticker
         );
-        YStatisticsResponse::from_json(send_scrape_request(&url).await?)
+        send_scrape_request(&url, ticker, YStatisticsResponse::from_json).await
+    }
+
+    /// URL of the chart of the given ticker from date start to end with the given interval
+    fn chart_period_url(
+        &self,
+        ticker: &str,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        interval: &str,
+    ) -> String {
+        format!(
+            YCHART_PERIOD_QUERY!(),
+            url = self.url,
+            symbol = ticker,
+            start = start.to_offset(UtcOffset::UTC).unix_timestamp(),
+            end = end.to_offset(UtcOffset::UTC).unix_timestamp(),
+            interval = interval
+        )
+    }
+
+    /// Retrieve the given comma separated modules (e.g. `defaultKeyStatistics`) from the quote summary API
+    async fn get_quote_summary<T: for<'de> serde::Deserialize<'de>>(
+        &self,
+        ticker: &str,
+        modules: &str,
+    ) -> Result<T, YahooError> {
+        let url = format!(
+            YQUOTE_SUMMARY_QUERY!(),
+            url = YQUOTE_SUMMARY_URL,
+            symbol = ticker,
+            modules = modules
+        );
+        send_request(&url, Some(ticker), |body| {
+            YQuoteSummaryResponse::<T>::from_slice(body)?.into_result()
+        })
+        .await
+    }
+
+    /// Retrieve the given modules from the quote summary API, or `None` if yahoo!
+    /// does not provide any of them for the given ticker
+    async fn get_optional_quote_summary<T: for<'de> serde::Deserialize<'de>>(
+        &self,
+        ticker: &str,
+        modules: &str,
+    ) -> Result<Option<T>, YahooError> {
+        match self.get_quote_summary(ticker, modules).await {
+            Err(YahooError::EmptyDataSet { .. }) => Ok(None),
+            result => result.map(Some),
+        }
     }
 
     /// Retrieve the list of quotes found searching a given name
     pub async fn search_ticker_opt(&self, name: &str) -> Result<YSearchResultOpt, YahooError> {
         let url = format!(YTICKER_QUERY!(), url = self.search_url, name = name);
-        YSearchResultOpt::from_json(send_request(&url).await?)
+        send_request(&url, None, YSearchResultOpt::from_slice).await
     }
 
     /// Retrieve the list of quotes found searching a given name
@@ -102,99 +292,219 @@ impl YahooConnector {
 
     /// https://query1.finance.yahoo.com/ws/fundamentals-timeseries/v1/finance/timeseries/NFL.AX?lang=en-AU&region=AU&symbol=NFL.AX&padTimeSeries=true&type=quarterlyTotalAssets,trailingTotalAssets,quarterlyStockholdersEquity,trailingStockholdersEquity,quarterlyGainsLossesNotAffectingRetainedEarnings,trailingGainsLossesNotAffectingRetainedEarnings,quarterlyRetainedEarnings,trailingRetainedEarnings,quarterlyCapitalStock,trailingCapitalStock,quarterlyTotalLiabilitiesNetMinorityInterest,trailingTotalLiabilitiesNetMinorityInterest,quarterlyTotalNonCurrentLiabilitiesNetMinorityInterest,trailingTotalNonCurrentLiabilitiesNetMinorityInterest,quarterlyOtherNonCurrentLiabilities,trailingOtherNonCurrentLiabilities,quarterlyNonCurrentDeferredRevenue,trailingNonCurrentDeferredRevenue,quarterlyNonCurrentDeferredTaxesLiabilities,trailingNonCurrentDeferredTaxesLiabilities,quarterlyLongTermDebt,trailingLongTermDebt,quarterlyCurrentLiabilities,trailingCurrentLiabilities,quarterlyOtherCurrentLiabilities,trailingOtherCurrentLiabilities,quarterlyCurrentDeferredRevenue,trailingCurrentDeferredRevenue,quarterlyCurrentAccruedExpenses,trailingCurrentAccruedExpenses,quarterlyIncomeTaxPayable,trailingIncomeTaxPayable,quarterlyAccountsPayable,trailingAccountsPayable,quarterlyCurrentDebt,trailingCurrentDebt,quarterlyTotalNonCurrentAssets,trailingTotalNonCurrentAssets,quarterlyOtherNonCurrentAssets,trailingOtherNonCurrentAssets,quarterlyOtherIntangibleAssets,trailingOtherIntangibleAssets,quarterlyGoodwill,trailingGoodwill,quarterlyInvestmentsAndAdvances,trailingInvestmentsAndAdvances,quarterlyNetPPE,trailingNetPPE,quarterlyAccumulatedDepreciation,trailingAccumulatedDepreciation,quarterlyGrossPPE,trailingGrossPPE,quarterlyCurrentAssets,trailingCurrentAssets,quarterlyOtherCurrentAssets,trailingOtherCurrentAssets,quarterlyInventory,trailingInventory,quarterlyAccountsReceivable,trailingAccountsReceivable,quarterlyCashCashEquivalentsAndShortTermInvestments,trailingCashCashEquivalentsAndShortTermInvestments,quarterlyOtherShortTermInvestments,trailingOtherShortTermInvestments,quarterlyCashAndCashEquivalents,trailingCashAndCashEquivalents&merge=false&period1=493590046&period2=1667449537&corsDomain=au.finance.yahoo.com
     pub async fn get_financials_timeseries(&self, symbol: &str, period: FinancialsPeriod) -> Result<HashMap<Date, FinancialReport>, YahooError> {
+        let types = FinancialReport::FIELDS
+            .iter()
+            .map(|field| format!("{}{}", period.prefix(), field))
+            .collect::<Vec<String>>();
+        let response = self.get_timeseries(symbol, &types).await?;
+        let mut reports = HashMap::<Date, FinancialReport>::new();
+        for result in response.timeseries.result {
+            let field = match result.key().and_then(|key| key.strip_prefix(period.prefix())) {
+                Some(field) => field.to_string(),
+                None => continue,
+            };
+            for value in result.values.iter().flatten() {
+                let report = reports.entry(value.as_of_date).or_default();
+                report.set(&field, Some(value.reported_value.raw));
+            }
+        }
+        Ok(reports)
+    }
+
+    /// Retrieve the history of the number of shares outstanding for the given ticker, sorted by date
+    pub async fn get_shares_outstanding_history(&self, symbol: &str, period: FinancialsPeriod) -> Result<Vec<SharesOutstanding>, YahooError> {
+        let reports = self.get_financials_timeseries(symbol, period).await?;
+        let mut history = reports
+            .into_iter()
+            .map(|(date, report)| SharesOutstanding::from_report(date, &report))
+            .filter(|shares| shares.shares().is_some())
+            .collect::<Vec<SharesOutstanding>>();
+        history.sort_unstable_by_key(|s| s.date);
+        Ok(history)
+    }
+
+    /// Retrieve the daily market capitalization and enterprise value for the given ticker
+    /// from date start to end (inclusive), based on the share counts, debt and cash
+    /// reported for the given period. The share counts are adjusted for the splits since
+    /// the first report, which are retrieved with quarterly quotes up to now.
+    pub async fn get_valuation_history(
+        &self,
+        ticker: &str,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        period: FinancialsPeriod,
+    ) -> Result<Vec<Valuation>, YahooError> {
+        let quotes = self.get_quote_history(ticker, start, end).await?.quotes()?;
+        let reports = self.get_financials_timeseries(ticker, period).await?;
+        let splits = match reports.keys().min() {
+            Some(first) => self
+                .get_quote_history_interval(ticker, first.midnight().assume_utc(), OffsetDateTime::now_utc(), "3mo")
+                .await?
+                .splits()?,
+            None => Vec::new(),
+        };
+        Ok(valuation_history(&quotes, &reports, &splits))
+    }
+
+    /// Retrieve the given types (e.g. `annualBasicAverageShares`) from the fundamentals timeseries API
+    async fn get_timeseries(&self, symbol: &str, types: &[String]) -> Result<YTimeseriesResponse, YahooError> {
         let ts = OffsetDateTime::now_utc().unix_timestamp();
-        let mut url: Url = format!("https://query1.finance.yahoo.com/ws/fundamentals-timeseries/v1/finance/timeseries/{symbol}").parse().unwrap();
+        let mut url: Url = format!("{}/{symbol}", YTIMESERIES_URL).parse().unwrap();
         {
             let mut query = url.query_pairs_mut();
             query.append_pair("lang", "en-AU");
             query.append_pair("region", "AU");
             query.append_pair("symbol", symbol);
             query.append_pair("padTimeSeries", "true");
-            query.append_pair("type", match period {
-                FinancialsPeriod::Quarterly => "quarterlyCurrentDebt,quarterlyCashAndCashEquivalents",
-                FinancialsPeriod::Annual => "annualCurrentDebt,annualCashAndCashEquivalents",
-                FinancialsPeriod::Trailing => "trailingCurrentDebt,trailingCashAndCashEquivalents",
-            });
+            query.append_pair("type", &types.join(","));
             query.append_pair("merge", "false");
             query.append_pair("period1", "0");
             query.append_pair("period2", &ts.to_string());
         }
-        let mut value = send_request(&url.to_string()).await?;
-        let mut value = value["timeseries"].take()["result"].take();
-        let data = value.as_array().unwrap();
-        let mut reports = HashMap::<Date, FinancialReport>::new();
-        for value in data {
-            let key = value["meta"]["type"].as_array().unwrap().first().and_then(|v| v.as_str()).unwrap();
-            let timestamp = value["timestamp"].as_array().cloned().unwrap_or_default().into_iter().map(|v| v.as_i64().unwrap()).map(|i| OffsetDateTime::from_unix_timestamp(i).unwrap());
-            let value = value[key].as_array().cloned().unwrap_or_default().into_iter().map(|v| v["reportedValue"]["raw"].as_f64());
-            for (timestamp, value) in timestamp.zip(value) {
-                let mut report = reports.entry(timestamp.date()).or_default();
-                if key.ends_with("CurrentDebt") {
-                    report.current_debt = value;
-                } else if key.ends_with("CashAndCashEquivalents") {
-                    report.cash_and_cash_equivalents = value;
-                }
-            }
-        }
-        Ok(reports)
+        send_request(url.as_str(), Some(symbol), YTimeseriesResponse::from_slice).await
     }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct FinancialReport {
     pub current_debt: Option<f64>,
+    pub total_debt: Option<f64>,
     pub cash_and_cash_equivalents: Option<f64>,
+    pub basic_average_shares: Option<f64>,
+    pub diluted_average_shares: Option<f64>,
+    pub ordinary_shares_number: Option<f64>,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl FinancialReport {
+    /// Timeseries types making up a report, without the period prefix
+    const FIELDS: [&'static str; 6] = [
+        "CurrentDebt",
+        "TotalDebt",
+        "CashAndCashEquivalents",
+        "BasicAverageShares",
+        "DilutedAverageShares",
+        "OrdinarySharesNumber",
+    ];
+
+    fn set(&mut self, field: &str, value: Option<f64>) {
+        match field {
+            "CurrentDebt" => self.current_debt = value,
+            "TotalDebt" => self.total_debt = value,
+            "CashAndCashEquivalents" => self.cash_and_cash_equivalents = value,
+            "BasicAverageShares" => self.basic_average_shares = value,
+            "DilutedAverageShares" => self.diluted_average_shares = value,
+            "OrdinarySharesNumber" => self.ordinary_shares_number = value,
+            _ => {}
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
 pub enum FinancialsPeriod {
     Quarterly,
     Annual,
     Trailing
 }
 
-/// Send request to yahoo! finance server and transform response to JSON value
-async fn send_request(url: &str) -> Result<serde_json::Value, YahooError> {
-    let resp = reqwest::get(url).compat().await;
-    if resp.is_err() {
-        return Err(YahooError::ConnectionFailed);
-    }
-    let resp = resp.unwrap();
-    match resp.status() {
-        StatusCode::OK => resp.json().await.map_err(|_| YahooError::InvalidJson),
-        status => Err(YahooError::FetchFailed(format!("Status Code: {}", status))),
+impl FinancialsPeriod {
+    /// Prefix of the timeseries types for this period
+    fn prefix(&self) -> &'static str {
+        match self {
+            FinancialsPeriod::Quarterly => "quarterly",
+            FinancialsPeriod::Annual => "annual",
+            FinancialsPeriod::Trailing => "trailing",
+        }
     }
 }
 
-/// Send request to yahoo! finance server and transform response to JSON value
-async fn send_scrape_request(url: &str) -> Result<serde_json::Value, YahooError> {
-    let resp = reqwest::get(url).compat().await;
-    if resp.is_err() {
-        return Err(YahooError::ConnectionFailed);
-    }
-    let resp = resp.unwrap();
-    let status = resp.status();
-    let html_text = resp.text().await.unwrap();
-    let json_str = html_text.split("root.App.main = ").nth(1)
+/// Send request to yahoo! finance server and parse the body of the response with the given function
+async fn send_request<T>(
+    url: &str,
+    symbol: Option<&str>,
+    parse: impl FnOnce(&[u8]) -> Result<T, YahooError>,
+) -> Result<T, YahooError> {
+    let context = RequestContext::new(url, symbol);
+    let body = fetch_body(url, context.clone()).await?;
+    let context = context.with_status(StatusCode::OK.as_u16()).with_body(&body);
+    parse(&body).map_err(|e| e.with_context(context))
+}
+
+/// Check if scraping the web page might succeed where the API failed with the given error.
+/// This isn't the case for unknown symbols, and scraping while rate limited makes it worse.
+fn scraping_may_help(err: &YahooError) -> bool {
+    matches!(
+        err,
+        YahooError::Unauthorized { .. }
+            | YahooError::ServerError { .. }
+            | YahooError::DeserializeFailed { .. }
+            | YahooError::InvalidJson { .. }
+    )
+}
+
+/// Send request for a yahoo! finance web page, extract the data stores embedded
+/// in the HTML and parse them with the given function
+async fn send_scrape_request<T>(
+    url: &str,
+    symbol: &str,
+    parse: impl FnOnce(serde_json::Value) -> Result<T, YahooError>,
+) -> Result<T, YahooError> {
+    let context = RequestContext::new(url, Some(symbol));
+    let body = fetch_body(url, context.clone()).await?;
+    let html_text = String::from_utf8_lossy(&body);
+    let context = context.with_status(StatusCode::OK.as_u16()).with_body(&body);
+    let json_str = html_text
+        .split("root.App.main = ")
+        .nth(1)
         .and_then(|o| o.split("(this)").next())
         .and_then(|o| o.split(";\n").next())
-        .map(|o| o.trim());
-    match status {
-        StatusCode::OK => if let Some(json_str) = json_str {
-            let json = serde_json::from_str::<serde_json::Value>(json_str).unwrap();
-            let stores = json.get("context")
-                .and_then(|json| json.get("dispatcher"))
-                .and_then(|json| json.get("stores"))
-                .unwrap()
-                .clone();
-            // println!("{}", stores);
-            serde_json::from_value(stores).map_err(|_| YahooError::InvalidJson)
-        } else {
-            Err(YahooError::FetchFailed("failed to find json in html".to_string()))
-        },
-        status => Err(YahooError::FetchFailed(format!("Status Code: {}", status))),
+        .map(|o| o.trim())
+        .ok_or_else(|| YahooError::fetch_failed("failed to find json in html", context.clone()))?;
+    let mut json = serde_json::from_str::<serde_json::Value>(json_str).map_err(|source| {
+        YahooError::InvalidJson {
+            source,
+            context: Box::new(context.clone()),
+        }
+    })?;
+    let stores = json
+        .pointer_mut("/context/dispatcher/stores")
+        .map(serde_json::Value::take)
+        .ok_or_else(|| YahooError::fetch_failed("failed to find stores in json", context.clone()))?;
+    parse(stores).map_err(|e| e.with_context(context))
+}
+
+/// Send request to yahoo! finance server and return the body of a successful response
+async fn fetch_body(url: &str, context: RequestContext) -> Result<Vec<u8>, YahooError> {
+    let connection_failed = |source: reqwest::Error, context: RequestContext| {
+        YahooError::ConnectionFailed {
+            source: Box::new(source),
+            context: Box::new(context),
+        }
+    };
+    let resp = match reqwest::get(url).compat().await {
+        Ok(resp) => resp,
+        Err(e) => return Err(connection_failed(e, context)),
+    };
+    let context = context.with_status(resp.status().as_u16());
+    if resp.status() != StatusCode::OK {
+        return Err(error_from_response(resp, context).await);
     }
-    
+    resp.bytes()
+        .await
+        .map(Vec::from)
+        .map_err(|e| connection_failed(e, context))
+}
+
+/// Map an unsuccessful response to the matching error, based on status, headers and body
+async fn error_from_response(resp: reqwest::Response, context: RequestContext) -> YahooError {
+    let retry_after = resp
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .map(Duration::from_secs);
+    let body = resp.text().await.unwrap_or_default();
+    YahooError::from_response(context, retry_after, &body)
 }
 
 #[cfg(test)]
@@ -222,6 +532,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scraping_may_help() {
+        let context = || Box::new(RequestContext::new("https://query1.finance.yahoo.com", Some("XXXX")));
+        assert!(scraping_may_help(&YahooError::Unauthorized { context: context() }));
+        assert!(scraping_may_help(&YahooError::ServerError {
+            status: 503,
+            context: context()
+        }));
+        let source = serde_json::from_str::<u32>("\"x\"").unwrap_err();
+        assert!(scraping_may_help(&YahooError::from(source)));
+        let source = serde_json::from_str::<u32>("{").unwrap_err();
+        assert!(scraping_may_help(&YahooError::from(source)));
+
+        assert!(!scraping_may_help(&YahooError::RateLimited {
+            retry_after: None,
+            context: context()
+        }));
+        assert!(!scraping_may_help(&YahooError::SymbolNotFound {
+            symbol: "XXXX".to_string(),
+            context: context()
+        }));
+    }
+
     #[test]
     fn test_get_statistics() {
         let provider = YahooConnector::new();
@@ -247,7 +580,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "DeserializeFailed(\"missing field `adjclose`\")")]
+    #[should_panic(expected = "missing field `adjclose`")]
     fn test_api_responses_missing_fields() {
         let provider = YahooConnector::new();
         let response = tokio_test::block_on(provider.get_latest_quotes("BF.B", "1m")).unwrap();
@@ -258,14 +591,20 @@ mod tests {
         let _ = response.last_quote().unwrap();
     }
 
+    #[test]
+    fn test_unknown_symbol() {
+        let provider = YahooConnector::new();
+        let response = tokio_test::block_on(provider.get_latest_quotes("UNKNOWN.XYZ", "1d"));
+        assert!(matches!(response, Err(YahooError::SymbolNotFound { symbol, .. }) if symbol == "UNKNOWN.XYZ"));
+    }
+
     #[test]
     fn test_get_quote_history() {
         let provider = YahooConnector::new();
         let start = datetime!(2020-1-1 00:00 UTC);
         let end = datetime!(2020-1-31 23:59:59.999 UTC);
         let resp = tokio_test::block_on(provider.get_quote_history("AAPL", start, end));
-        if resp.is_ok() {
-            let resp = resp.unwrap();
+        if let Ok(resp) = resp {
             assert_eq!(resp.chart.result[0].timestamp.len(), 21);
             let quotes = resp.quotes().unwrap();
             assert_eq!(quotes.len(), 21);
@@ -303,7 +642,7 @@ mod tests {
         let response =
             tokio_test::block_on(provider.get_quote_range("BTC-USD", "1d", "5d")).unwrap();
         let quotes = response.quotes().unwrap();
-        assert!(quotes.len() > 0usize);
+        assert!(!quotes.is_empty());
     }
 
     #[test]
@@ -328,8 +667,7 @@ mod tests {
         let start = datetime!(2020-1-1 00:00 UTC);
         let end = datetime!(2020-1-31 23:59:59.999 UTC);
         let resp = tokio_test::block_on(provider.get_quote_history("VTSAX", start, end));
-        if resp.is_ok() {
-            let resp = resp.unwrap();
+        if let Ok(resp) = resp {
             assert_eq!(resp.chart.result[0].timestamp.len(), 21);
             let quotes = resp.quotes().unwrap();
             assert_eq!(quotes.len(), 21);
@@ -349,6 +687,35 @@ mod tests {
     }
 
     
+    #[test]
+    fn test_get_fund_data() {
+        let provider = YahooConnector::new();
+        let response = tokio_test::block_on(provider.get_fund_data("VTSAX")).unwrap();
+        assert!(response.inception_date().is_some());
+        let profile = response.fund_profile.unwrap();
+        assert_eq!(profile.family.as_deref(), Some("Vanguard"));
+        assert!(!response.top_holdings.unwrap().holdings.is_empty());
+        assert!(response.fund_performance.is_some());
+    }
+
+    #[test]
+    fn test_get_esg_scores() {
+        let provider = YahooConnector::new();
+        let esg = tokio_test::block_on(provider.get_esg_scores("AAPL")).unwrap().unwrap();
+        assert!(esg.total_esg.unwrap().0.is_some());
+        let esg = tokio_test::block_on(provider.get_esg_scores("VTSAX")).unwrap();
+        println!("{esg:?}");
+    }
+
+    #[test]
+    fn test_get_sec_filings() {
+        let provider = YahooConnector::new();
+        let filings = tokio_test::block_on(provider.get_sec_filings("AAPL")).unwrap();
+        assert!(!filings.is_empty());
+        assert!(filings.windows(2).all(|w| w[0].date >= w[1].date));
+        assert!(filings.iter().any(|f| f.filing_type == SecFilingType::AnnualReport || f.filing_type == SecFilingType::QuarterlyReport));
+    }
+
     #[test]
     fn test_mutual_fund_range() {
         let provider = YahooConnector::new();
@@ -366,5 +733,61 @@ mod tests {
         let response = tokio_test::block_on(provider.get_financials_timeseries("NFL.AX", FinancialsPeriod::Annual)).unwrap();
         println!("{response:#?}");
         let response = tokio_test::block_on(provider.get_financials_timeseries("NFL.AX", FinancialsPeriod::Quarterly)).unwrap();
+        println!("{response:#?}");
+    }
+
+    #[test]
+    fn test_get_shares_outstanding_history() {
+        let provider = YahooConnector::new();
+        let history = tokio_test::block_on(provider.get_shares_outstanding_history("AAPL", FinancialsPeriod::Annual)).unwrap();
+        assert!(!history.is_empty());
+        assert!(history.windows(2).all(|w| w[0].date < w[1].date));
+        assert!(history.iter().all(|s| s.shares().is_some()));
+    }
+
+    #[test]
+    fn test_get_quote_history_with_comparisons() {
+        let provider = YahooConnector::new();
+        let start = datetime!(2020-1-1 00:00 UTC);
+        let end = datetime!(2020-1-31 23:59:59.999 UTC);
+        let response = tokio_test::block_on(
+            provider.get_quote_history_with_comparisons("AAPL", &["^GSPC", "MSFT"], start, end, "1d"),
+        )
+        .unwrap();
+        let block = response.first_result().unwrap();
+        let gspc = block.comparison("^GSPC").unwrap();
+        assert_eq!(gspc.close.len(), block.timestamp.len());
+        assert!(block.comparison("MSFT").is_some());
+    }
+
+    #[test]
+    fn test_get_corporate_actions() {
+        let provider = YahooConnector::new();
+        let start = datetime!(2020-1-1 00:00 UTC);
+        let end = datetime!(2020-12-31 23:59:59.999 UTC);
+        let actions = tokio_test::block_on(provider.get_corporate_actions("AAPL", start, end)).unwrap();
+        assert!(actions.windows(2).all(|w| w[0].date <= w[1].date));
+        assert!(actions.iter().any(|a| matches!(a.kind, CorporateActionKind::Split(_))));
+        assert_eq!(actions.last().unwrap().cumulative_split_factor, 4.0);
+    }
+
+    #[test]
+    fn test_adjclose_deviation() {
+        // the prices of yahoo! are already adjusted for the 4:1 split of 2020-08-31
+        let provider = YahooConnector::new();
+        let start = datetime!(2020-1-1 00:00 UTC);
+        let end = datetime!(2020-12-31 23:59:59.999 UTC);
+        let resp = tokio_test::block_on(provider.get_quote_history("AAPL", start, end)).unwrap();
+        assert!(resp.adjclose_deviation().unwrap().unwrap() < 1e-3);
+    }
+
+    #[test]
+    fn test_get_valuation_history() {
+        let provider = YahooConnector::new();
+        let start = datetime!(2021-1-1 00:00 UTC);
+        let end = datetime!(2021-12-31 23:59:59.999 UTC);
+        let valuations = tokio_test::block_on(provider.get_valuation_history("AAPL", start, end, FinancialsPeriod::Quarterly)).unwrap();
+        assert!(!valuations.is_empty());
+        assert!(valuations.iter().all(|v| v.market_cap > 0.0));
     }
 }
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use super::YahooError;
+use crate::adjustment::{distribution_factor, split_ratio};
+use crate::quotes::{CapitalGain, Dividend, Split, YQuoteBlock, YResponse};
+use crate::utils::datetime_from_timestamp;
+
+/// Kind of a corporate action with its details
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CorporateActionKind {
+    Dividend(Dividend),
+    CapitalGain(CapitalGain),
+    Split(Split),
+}
+
+/// Entry of a ledger of corporate actions
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CorporateAction {
+    /// Unix timestamp (seconds since the epoch, UTC) of the action, i.e. the ex-date of
+    /// distributions
+    pub date: i64,
+    pub kind: CorporateActionKind,
+    /// Number of shares held after this action per share held at the start of the ledger,
+    /// i.e. the product of the ratios of all splits up to and including this action
+    pub cumulative_split_factor: f64,
+    /// Factor to back-adjust prices before this action for it: `1 - amount / close` for
+    /// distributions using the last close before the ex-date, or `None` without a known
+    /// close before it. It is 1 for splits, since the prices of yahoo! are already adjusted
+    /// for splits.
+    pub adjustment_factor: Option<f64>,
+}
+
+impl CorporateAction {
+    /// Date and time of the action
+    pub fn datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.date)
+    }
+}
+
+impl YResponse {
+    /// Ledger of corporate actions, see [`YQuoteBlock::corporate_actions`]
+    pub fn corporate_actions(&self) -> Result<Vec<CorporateAction>, YahooError> {
+        self.first_result()?.corporate_actions()
+    }
+}
+
+impl YQuoteBlock {
+    /// Splits, dividends and capital gains contained in the block in chronological order.
+    /// Distributions are listed before splits of the same date, since the shares held
+    /// before the split are entitled to them.
+    pub fn corporate_actions(&self) -> Result<Vec<CorporateAction>, YahooError> {
+        let series = self.quote_series()?;
+        self.corporate_actions_with_closes(series.timestamps(), series.close())
+    }
+
+    /// Corporate actions contained in the block like [`YQuoteBlock::corporate_actions`], with
+    /// the adjustment factors of distributions based on the given closing prices with
+    /// ascending timestamps instead of the quotes of the block, e.g. of daily quotes
+    pub(crate) fn corporate_actions_with_closes(
+        &self,
+        timestamps: &[i64],
+        close: &[Option<f64>],
+    ) -> Result<Vec<CorporateAction>, YahooError> {
+        let distribution = |date: i64, amount: f64| distribution_factor(timestamps, close, date, amount);
+
+        let mut actions: Vec<(usize, i64, CorporateActionKind, Option<f64>)> = Vec::new();
+        for dividend in self.dividends()? {
+            let factor = distribution(dividend.date, dividend.amount);
+            actions.push((0, dividend.date, CorporateActionKind::Dividend(dividend), factor));
+        }
+        for gain in self.capital_gains()? {
+            let factor = distribution(gain.date, gain.amount);
+            actions.push((1, gain.date, CorporateActionKind::CapitalGain(gain), factor));
+        }
+        for split in self.splits()? {
+            actions.push((2, split.date, CorporateActionKind::Split(split), Some(1.0)));
+        }
+        actions.sort_by_key(|(order, date, _, _)| (*date, *order));
+
+        let mut cumulative_split_factor = 1.0;
+        Ok(actions
+            .into_iter()
+            .map(|(_, date, kind, adjustment_factor)| {
+                if let CorporateActionKind::Split(split) = &kind {
+                    cumulative_split_factor *= split_ratio(split).unwrap_or(1.0);
+                }
+                CorporateAction {
+                    date,
+                    kind,
+                    cumulative_split_factor,
+                    adjustment_factor,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quotes::tests::chart_json;
+
+    #[test]
+    fn test_corporate_actions() {
+        let mut json = chart_json();
+        let events = &mut json["chart"]["result"][0]["events"];
+        events["splits"]["1593783000"] = serde_json::json!({
+            "date": 1593783000, "numerator": 1, "denominator": 4, "splitRatio": "1:4"
+        });
+        events["capitalGains"] = serde_json::json!({
+            "1593783000": { "amount": 2.4, "date": 1593783000 }
+        });
+        let response = YResponse::from_json(json).unwrap();
+        let actions = response.corporate_actions().unwrap();
+
+        assert_eq!(actions.len(), 4);
+        assert!(matches!(actions[0].kind, CorporateActionKind::Split(_)));
+        assert_eq!(actions[0].cumulative_split_factor, 2.0);
+        assert_eq!(actions[0].adjustment_factor, Some(1.0));
+
+        assert!(matches!(actions[1].kind, CorporateActionKind::Dividend(_)));
+        assert_eq!(actions[1].adjustment_factor, Some(1.0 - 1.63 / 119.7));
+        assert_eq!(actions[1].cumulative_split_factor, 2.0);
+
+        assert!(matches!(actions[2].kind, CorporateActionKind::CapitalGain(_)));
+        assert_eq!(actions[2].adjustment_factor, Some(1.0 - 2.4 / 121.1));
+        assert!(matches!(actions[3].kind, CorporateActionKind::Split(_)));
+        assert_eq!(actions[3].cumulative_split_factor, 0.5);
+        assert_eq!(actions[3].adjustment_factor, Some(1.0));
+
+        // the factors of distributions can be based on the closes of other quotes
+        let block = response.first_result().unwrap();
+        let actions = block.corporate_actions_with_closes(&[1593610200], &[Some(100.0)]).unwrap();
+        assert_eq!(actions[1].adjustment_factor, Some(1.0 - 1.63 / 100.0));
+        let actions = block.corporate_actions_with_closes(&[], &[]).unwrap();
+        assert_eq!(actions[2].adjustment_factor, None);
+        assert_eq!(actions[3].cumulative_split_factor, 0.5);
+
+        let json = serde_json::to_value(&actions[0]).unwrap();
+        assert_eq!(json["kind"]["type"], "split");
+        assert_eq!(json["kind"]["split_ratio"], "2:1");
+        let action: CorporateAction = serde_json::from_value(json).unwrap();
+        assert!(matches!(action.kind, CorporateActionKind::Split(split) if split.split_ratio == "2:1"));
+    }
+}
@@ -0,0 +1,343 @@
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use super::YahooError;
+use crate::quotes::{Quote, YMetaData, YResponse};
+use crate::utils::datetime_from_timestamp;
+
+/// Column of a CSV file of quotes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvColumn {
+    Date,
+    Open,
+    High,
+    Low,
+    Close,
+    AdjClose,
+    Volume,
+}
+
+impl CsvColumn {
+    /// All columns in the order used by default
+    pub const ALL: [CsvColumn; 7] = [
+        CsvColumn::Date,
+        CsvColumn::Open,
+        CsvColumn::High,
+        CsvColumn::Low,
+        CsvColumn::Close,
+        CsvColumn::AdjClose,
+        CsvColumn::Volume,
+    ];
+
+    /// Name of the column in the header of the CSV file
+    pub fn name(&self) -> &'static str {
+        match self {
+            CsvColumn::Date => "date",
+            CsvColumn::Open => "open",
+            CsvColumn::High => "high",
+            CsvColumn::Low => "low",
+            CsvColumn::Close => "close",
+            CsvColumn::AdjClose => "adjclose",
+            CsvColumn::Volume => "volume",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<CsvColumn> {
+        CsvColumn::ALL
+            .iter()
+            .find(|column| column.name().eq_ignore_ascii_case(name.trim()))
+            .copied()
+    }
+}
+
+/// Format of the dates written to CSV files
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvDateFormat {
+    /// Unix timestamp in seconds
+    Timestamp,
+    /// RFC 3339 date and time in UTC, e.g. `2020-07-01T13:30:00Z`
+    Utc,
+    /// RFC 3339 date and time in the local time of the exchange, e.g. `2020-07-01T09:30:00-04:00`
+    ExchangeLocal,
+}
+
+/// Options for writing and reading CSV files of quotes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Columns of quotes to be written, in the given order
+    pub columns: Vec<CsvColumn>,
+    pub date_format: CsvDateFormat,
+    pub delimiter: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            columns: CsvColumn::ALL.to_vec(),
+            date_format: CsvDateFormat::Utc,
+            delimiter: b',',
+        }
+    }
+}
+
+/// Options for writing CSV files of splits and dividends, which have fixed columns
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvEventOptions {
+    pub date_format: CsvDateFormat,
+    pub delimiter: u8,
+}
+
+impl Default for CsvEventOptions {
+    fn default() -> Self {
+        CsvEventOptions {
+            date_format: CsvDateFormat::Utc,
+            delimiter: b',',
+        }
+    }
+}
+
+impl From<&CsvOptions> for CsvEventOptions {
+    /// Date format and delimiter of the options for quotes
+    fn from(options: &CsvOptions) -> Self {
+        CsvEventOptions {
+            date_format: options.date_format,
+            delimiter: options.delimiter,
+        }
+    }
+}
+
+impl CsvDateFormat {
+    fn format(&self, timestamp: i64, meta: &YMetaData) -> Result<String, YahooError> {
+        let datetime = match self {
+            CsvDateFormat::Timestamp => return Ok(timestamp.to_string()),
+            CsvDateFormat::Utc => datetime_from_timestamp(timestamp),
+            CsvDateFormat::ExchangeLocal => meta.to_exchange_time(timestamp),
+        };
+        datetime.format(&Rfc3339).map_err(csv_failed)
+    }
+}
+
+fn csv_writer<W: Write>(writer: W, delimiter: u8) -> csv::Writer<W> {
+    csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(writer)
+}
+
+impl YResponse {
+    /// Write the quotes as CSV with the columns, date format and delimiter of the given options
+    pub fn write_quotes_csv<W: Write>(&self, writer: W, options: &CsvOptions) -> Result<(), YahooError> {
+        let quotes = self.quotes()?;
+        let meta = &self.first_result()?.meta;
+        let mut writer = csv_writer(writer, options.delimiter);
+        writer
+            .write_record(options.columns.iter().map(|column| column.name()))
+            .map_err(csv_failed)?;
+        for quote in &quotes {
+            let mut record = Vec::with_capacity(options.columns.len());
+            for column in &options.columns {
+                record.push(match column {
+                    CsvColumn::Date => options.date_format.format(quote.timestamp, meta)?,
+                    CsvColumn::Open => quote.open.to_string(),
+                    CsvColumn::High => quote.high.to_string(),
+                    CsvColumn::Low => quote.low.to_string(),
+                    CsvColumn::Close => quote.close.to_string(),
+                    CsvColumn::AdjClose => quote.adjclose.to_string(),
+                    CsvColumn::Volume => quote.volume.to_string(),
+                });
+            }
+            writer.write_record(&record).map_err(csv_failed)?;
+        }
+        writer.flush().map_err(csv_failed)
+    }
+
+    /// Write the splits as CSV with the columns `date`, `numerator`, `denominator` and `split_ratio`,
+    /// using the date format and delimiter of the given options
+    pub fn write_splits_csv<W: Write>(&self, writer: W, options: &CsvEventOptions) -> Result<(), YahooError> {
+        let splits = self.splits()?;
+        let meta = &self.first_result()?.meta;
+        let mut writer = csv_writer(writer, options.delimiter);
+        writer
+            .write_record(["date", "numerator", "denominator", "split_ratio"])
+            .map_err(csv_failed)?;
+        for split in &splits {
+            writer
+                .write_record([
+                    options.date_format.format(split.date, meta)?,
+                    split.numerator.to_string(),
+                    split.denominator.to_string(),
+                    split.split_ratio.clone(),
+                ])
+                .map_err(csv_failed)?;
+        }
+        writer.flush().map_err(csv_failed)
+    }
+
+    /// Write the dividends as CSV with the columns `date` (the ex-dividend date) and `amount`,
+    /// using the date format and delimiter of the given options
+    pub fn write_dividends_csv<W: Write>(&self, writer: W, options: &CsvEventOptions) -> Result<(), YahooError> {
+        let dividends = self.dividends()?;
+        let meta = &self.first_result()?.meta;
+        let mut writer = csv_writer(writer, options.delimiter);
+        writer.write_record(["date", "amount"]).map_err(csv_failed)?;
+        for dividend in &dividends {
+            writer
+                .write_record([
+                    options.date_format.format(dividend.date, meta)?,
+                    dividend.amount.to_string(),
+                ])
+                .map_err(csv_failed)?;
+        }
+        writer.flush().map_err(csv_failed)
+    }
+}
+
+/// Read quotes from CSV, e.g. as written by [`YResponse::write_quotes_csv`], using the delimiter
+/// of the given options. The columns are identified by the header, so their order does not matter.
+/// A `date` and a `close` column are required; other missing columns are filled with zero.
+/// Dates may be unix timestamps or RFC 3339 dates and times with any offset.
+pub fn read_quotes_csv<R: Read>(reader: R, options: &CsvOptions) -> Result<Vec<Quote>, YahooError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .from_reader(reader);
+    let columns: Vec<Option<CsvColumn>> = reader
+        .headers()
+        .map_err(csv_failed)?
+        .iter()
+        .map(CsvColumn::from_name)
+        .collect();
+    for required in [CsvColumn::Date, CsvColumn::Close] {
+        if !columns.contains(&Some(required)) {
+            return Err(csv_failed(format!("missing column `{}`", required.name())));
+        }
+    }
+
+    let mut quotes = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_failed)?;
+        let mut quote = Quote {
+            timestamp: 0,
+            open: 0.0,
+            high: 0.0,
+            low: 0.0,
+            volume: 0,
+            close: 0.0,
+            adjclose: 0.0,
+        };
+        for (column, field) in columns.iter().zip(record.iter()) {
+            let field = field.trim();
+            match column {
+                Some(CsvColumn::Date) => quote.timestamp = parse_date(field)?,
+                Some(CsvColumn::Open) => quote.open = parse_field(field)?,
+                Some(CsvColumn::High) => quote.high = parse_field(field)?,
+                Some(CsvColumn::Low) => quote.low = parse_field(field)?,
+                Some(CsvColumn::Close) => quote.close = parse_field(field)?,
+                Some(CsvColumn::AdjClose) => quote.adjclose = parse_field(field)?,
+                Some(CsvColumn::Volume) => quote.volume = parse_field(field)?,
+                None => {}
+            }
+        }
+        quotes.push(quote);
+    }
+    Ok(quotes)
+}
+
+fn parse_date(field: &str) -> Result<i64, YahooError> {
+    match field.parse::<i64>() {
+        Ok(timestamp) => Ok(timestamp),
+        Err(_) => OffsetDateTime::parse(field, &Rfc3339)
+            .map(|datetime| datetime.unix_timestamp())
+            .map_err(|_| csv_failed(format!("invalid date `{}`", field))),
+    }
+}
+
+fn parse_field<T: std::str::FromStr>(field: &str) -> Result<T, YahooError> {
+    field
+        .parse()
+        .map_err(|_| csv_failed(format!("invalid value `{}`", field)))
+}
+
+fn csv_failed<E>(source: E) -> YahooError
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    YahooError::CsvFailed {
+        source: source.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quotes::tests::chart_json;
+
+    #[test]
+    fn test_quotes_csv_round_trip() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        for date_format in [CsvDateFormat::Timestamp, CsvDateFormat::Utc, CsvDateFormat::ExchangeLocal] {
+            let options = CsvOptions {
+                date_format,
+                delimiter: b';',
+                ..CsvOptions::default()
+            };
+            let mut csv = Vec::new();
+            response.write_quotes_csv(&mut csv, &options).unwrap();
+            let quotes = read_quotes_csv(csv.as_slice(), &options).unwrap();
+            assert_eq!(quotes, response.quotes().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_quotes_csv_columns() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let options = CsvOptions {
+            columns: vec![CsvColumn::Date, CsvColumn::Close, CsvColumn::Volume],
+            date_format: CsvDateFormat::ExchangeLocal,
+            delimiter: b',',
+        };
+        let mut csv = Vec::new();
+        response.write_quotes_csv(&mut csv, &options).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("date,close,volume"));
+        assert_eq!(lines.next(), Some("2020-07-01T09:30:00-04:00,119.7,3998500"));
+
+        let quotes = read_quotes_csv(csv.as_bytes(), &options).unwrap();
+        assert_eq!(quotes[0].timestamp, 1593610200);
+        assert_eq!(quotes[0].open, 0.0);
+
+        let error = read_quotes_csv("date,open\n1593610200,1.0\n".as_bytes(), &options);
+        assert!(matches!(error, Err(YahooError::CsvFailed { .. })));
+    }
+
+    #[test]
+    fn test_events_csv() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let options = CsvEventOptions::default();
+        let mut csv = Vec::new();
+        response.write_dividends_csv(&mut csv, &options).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().next(), Some("date,amount"));
+        assert!(csv.lines().nth(1).unwrap().ends_with(",1.63"));
+
+        let mut csv = Vec::new();
+        response.write_splits_csv(&mut csv, &options).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().next(), Some("date,numerator,denominator,split_ratio"));
+        assert_eq!(csv.lines().count(), 2);
+
+        let options = CsvEventOptions::from(&CsvOptions {
+            columns: vec![CsvColumn::Date, CsvColumn::Close],
+            date_format: CsvDateFormat::Timestamp,
+            delimiter: b';',
+        });
+        let mut csv = Vec::new();
+        response.write_dividends_csv(&mut csv, &options).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv, "date;amount\n1593696600;1.63\n");
+    }
+}
@@ -0,0 +1,167 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{RequestContext, YahooError};
+use crate::quotes::{DecimalQuote, YChartError};
+use crate::utils::{null_as_default, price_hint_decimals, round_price};
+
+/// Chart response with the prices parsed exactly from the numbers in the JSON text,
+/// rather than via `f64` like [`YResponse`](crate::YResponse)
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YDecimalResponse {
+    pub chart: YDecimalChart,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YDecimalChart {
+    #[serde(deserialize_with = "null_as_default")]
+    pub result: Vec<YDecimalQuoteBlock>,
+    pub error: Option<YChartError>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YDecimalQuoteBlock {
+    pub meta: YDecimalMetaData,
+    pub timestamp: Vec<i64>,
+    pub indicators: DecimalQuoteBlock,
+}
+
+/// Meta data of a chart result needed for decimal quotes
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
+pub struct YDecimalMetaData {
+    pub symbol: String,
+    #[serde(alias = "price_hint")]
+    pub price_hint: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DecimalQuoteBlock {
+    pub(crate) quote: Vec<DecimalQuoteList>,
+    #[serde(default)]
+    pub(crate) adjclose: Option<Vec<DecimalAdjClose>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DecimalQuoteList {
+    #[serde(default)]
+    pub volume: Vec<Option<u64>>,
+    #[serde(default)]
+    pub open: Vec<Option<Decimal>>,
+    #[serde(default)]
+    pub high: Vec<Option<Decimal>>,
+    #[serde(default)]
+    pub low: Vec<Option<Decimal>>,
+    #[serde(default)]
+    pub close: Vec<Option<Decimal>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DecimalAdjClose {
+    pub(crate) adjclose: Vec<Option<Decimal>>,
+}
+
+impl YDecimalResponse {
+    /// Deserialize the response from the bytes of the body. The prices are parsed
+    /// from the JSON text, so they are exact up to the precision of `Decimal`.
+    pub fn from_slice(body: &[u8]) -> Result<YDecimalResponse, YahooError> {
+        let response: YDecimalResponse = serde_json::from_slice(body)
+            .map_err(YahooError::from)?;
+        match &response.chart.error {
+            Some(error) => Err(YahooError::from_api_error(
+                &error.code,
+                &error.description,
+                RequestContext::default(),
+            )),
+            None => Ok(response),
+        }
+    }
+
+    /// Quotes with decimal prices, rounded to the number of decimal places given by
+    /// the `priceHint` of the response, see [`YDecimalQuoteBlock::decimal_quotes`]
+    pub fn decimal_quotes(&self) -> Result<Vec<DecimalQuote>, YahooError> {
+        self.chart
+            .result
+            .first()
+            .ok_or_else(YahooError::empty_data_set)?
+            .decimal_quotes()
+    }
+}
+
+impl YDecimalQuoteBlock {
+    /// Quotes with decimal prices, rounded to the number of decimal places given by
+    /// the `priceHint` of the block. Like [`YQuoteBlock::quotes`](crate::YQuoteBlock::quotes),
+    /// quotes without close are skipped; other missing values are `None`.
+    pub fn decimal_quotes(&self) -> Result<Vec<DecimalQuote>, YahooError> {
+        let n = self.timestamp.len();
+        if n == 0 {
+            return Err(YahooError::empty_data_set());
+        }
+        let quote = self
+            .indicators
+            .quote
+            .first()
+            .ok_or_else(YahooError::data_inconsistency)?;
+        let adjclose = self
+            .indicators
+            .adjclose
+            .as_ref()
+            .map(|adjclose| adjclose.first().map(|adjclose| &adjclose.adjclose));
+        if [quote.open.len(), quote.high.len(), quote.low.len(), quote.close.len(), quote.volume.len()]
+            .iter()
+            .any(|len| *len != n)
+            || adjclose.is_some_and(|adjclose| adjclose.is_none_or(|adjclose| adjclose.len() != n))
+        {
+            return Err(YahooError::data_inconsistency());
+        }
+
+        let decimals = price_hint_decimals(self.meta.price_hint);
+        let price = |value: Option<Decimal>| value.map(|value| round_price(value, decimals));
+        Ok((0..n)
+            .filter_map(|i| {
+                let close = quote.close[i]?;
+                Some(DecimalQuote {
+                    timestamp: self.timestamp[i],
+                    open: price(quote.open[i]),
+                    high: price(quote.high[i]),
+                    low: price(quote.low[i]),
+                    volume: quote.volume[i],
+                    close: round_price(close, decimals),
+                    adjclose: price(adjclose.flatten().and_then(|adjclose| adjclose[i])),
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quotes::tests::chart_json;
+    use crate::YResponse;
+
+    #[test]
+    fn test_exact_decimal_quotes() {
+        let body = chart_json()
+            .to_string()
+            .replace("119.7", "1.0049999999999999")
+            .replace("120.3", "0.30000000000000004441");
+        let response = YDecimalResponse::from_slice(body.as_bytes()).unwrap();
+        let quotes = response.decimal_quotes().unwrap();
+        assert_eq!(quotes.len(), 3);
+        // the shortest representation of the closest f64 is 1.005, which rounds up
+        assert_eq!(quotes[0].close, Decimal::new(100, 2));
+        assert_eq!(YResponse::from_slice(body.as_bytes()).unwrap().decimal_quotes().unwrap()[0].close, Decimal::new(101, 2));
+        assert_eq!(quotes[0].open, Some(Decimal::new(30, 2)));
+        assert_eq!(quotes[1].low, None);
+        assert_eq!(quotes[1].volume, None);
+        assert_eq!(quotes[2].adjclose, Some(Decimal::new(11440, 2)));
+        assert_eq!(response.chart.result[0].indicators.quote[0].open[0], Some("0.30000000000000004441".parse().unwrap()));
+
+        let body = r#"{"chart":{"result":null,"error":{"code":"Not Found","description":"No data found, symbol may be delisted"}}}"#;
+        assert!(matches!(
+            YDecimalResponse::from_slice(body.as_bytes()),
+            Err(YahooError::SymbolNotFound { .. })
+        ));
+    }
+}
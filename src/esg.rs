@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::DecimalValue;
+
+/// Module `esgScores` of the quote summary, as provided by Sustainalytics.
+/// Scores are risk scores, i.e. lower values are better.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct EsgScores {
+    #[serde(alias = "total_esg")]
+    pub total_esg: Option<DecimalValue>,
+    #[serde(alias = "environment_score")]
+    pub environment_score: Option<DecimalValue>,
+    #[serde(alias = "social_score")]
+    pub social_score: Option<DecimalValue>,
+    #[serde(alias = "governance_score")]
+    pub governance_score: Option<DecimalValue>,
+    /// Percentile of the total ESG score within the peer group
+    pub percentile: Option<DecimalValue>,
+    #[serde(alias = "environment_percentile")]
+    pub environment_percentile: Option<DecimalValue>,
+    #[serde(alias = "social_percentile")]
+    pub social_percentile: Option<DecimalValue>,
+    #[serde(alias = "governance_percentile")]
+    pub governance_percentile: Option<DecimalValue>,
+    #[serde(alias = "rating_year")]
+    pub rating_year: Option<i32>,
+    #[serde(alias = "rating_month")]
+    pub rating_month: Option<u8>,
+    /// Highest controversy level from 0 (none) to 5 (severe)
+    #[serde(alias = "highest_controversy")]
+    pub highest_controversy: Option<f64>,
+    #[serde(alias = "related_controversy")]
+    pub related_controversy: Option<Vec<String>>,
+    /// Performance relative to the peer group, e.g. `UNDER_PERF`
+    #[serde(alias = "esg_performance")]
+    pub esg_performance: Option<String>,
+    #[serde(alias = "peer_group")]
+    pub peer_group: Option<String>,
+    #[serde(alias = "peer_count")]
+    pub peer_count: Option<u32>,
+    #[serde(alias = "peer_esg_score_performance")]
+    pub peer_esg_score_performance: Option<PeerPerformance>,
+    #[serde(alias = "peer_environment_performance")]
+    pub peer_environment_performance: Option<PeerPerformance>,
+    #[serde(alias = "peer_social_performance")]
+    pub peer_social_performance: Option<PeerPerformance>,
+    #[serde(alias = "peer_governance_performance")]
+    pub peer_governance_performance: Option<PeerPerformance>,
+    #[serde(alias = "peer_highest_controversy_performance")]
+    pub peer_highest_controversy_performance: Option<PeerPerformance>,
+    pub adult: Option<bool>,
+    pub alcoholic: Option<bool>,
+    #[serde(alias = "animal_testing")]
+    pub animal_testing: Option<bool>,
+    pub catholic: Option<bool>,
+    pub coal: Option<bool>,
+    #[serde(alias = "controversial_weapons")]
+    pub controversial_weapons: Option<bool>,
+    #[serde(alias = "fur_leather")]
+    pub fur_leather: Option<bool>,
+    pub gambling: Option<bool>,
+    pub gmo: Option<bool>,
+    #[serde(alias = "military_contract")]
+    pub military_contract: Option<bool>,
+    pub nuclear: Option<bool>,
+    #[serde(alias = "palm_oil")]
+    pub palm_oil: Option<bool>,
+    pub pesticides: Option<bool>,
+    #[serde(alias = "small_arms")]
+    pub small_arms: Option<bool>,
+    pub tobacco: Option<bool>,
+}
+
+/// Statistics of a score within the peer group
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct PeerPerformance {
+    pub min: Option<f64>,
+    pub avg: Option<f64>,
+    pub max: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub(crate) struct YEsgData {
+    pub esg_scores: Option<EsgScores>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quote_summary::YQuoteSummaryResponse;
+    use crate::utils::tests::assert_serde_round_trip;
+    use crate::YahooError;
+
+    #[test]
+    fn test_deserialize_esg_scores() {
+        let json = serde_json::json!({
+            "quoteSummary": {
+                "result": [{
+                    "esgScores": {
+                        "maxAge": 86400,
+                        "totalEsg": { "raw": 16.68, "fmt": "16.7" },
+                        "environmentScore": { "raw": 0.65, "fmt": "0.7" },
+                        "ratingYear": 2022,
+                        "ratingMonth": 9,
+                        "highestControversy": 3,
+                        "peerCount": 55,
+                        "esgPerformance": "UNDER_PERF",
+                        "peerGroup": "Technology Hardware",
+                        "relatedControversy": ["Customer Incidents"],
+                        "peerEsgScorePerformance": { "min": 6.88, "avg": 16.97, "max": 27.86 },
+                        "percentile": { "raw": 14.14, "fmt": "14" },
+                        "environmentPercentile": null,
+                        "tobacco": false,
+                        "controversialWeapons": false
+                    }
+                }],
+                "error": null
+            }
+        });
+        let esg = YQuoteSummaryResponse::<YEsgData>::from_slice(json.to_string().as_bytes())
+            .unwrap()
+            .into_result()
+            .unwrap()
+            .esg_scores
+            .unwrap();
+        assert_serde_round_trip(&esg);
+        assert!(esg.total_esg.unwrap().0.is_some());
+        assert!(esg.environment_percentile.is_none());
+        assert_eq!(esg.rating_year, Some(2022));
+        assert_eq!(esg.highest_controversy, Some(3.0));
+        assert_eq!(esg.peer_esg_score_performance.unwrap().avg, Some(16.97));
+        assert_eq!(esg.tobacco, Some(false));
+        assert_eq!(esg.gambling, None);
+    }
+
+    #[test]
+    fn test_missing_esg_scores() {
+        let json = serde_json::json!({
+            "quoteSummary": {
+                "result": null,
+                "error": {
+                    "code": "Not Found",
+                    "description": "No fundamentals data found for any of the summaryTypes=esgScores"
+                }
+            }
+        });
+        let esg = YQuoteSummaryResponse::<YEsgData>::from_slice(json.to_string().as_bytes())
+            .unwrap()
+            .into_result();
+        assert!(matches!(esg, Err(YahooError::EmptyDataSet { .. })));
+    }
+}
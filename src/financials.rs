@@ -1,44 +1,81 @@
-use serde::Deserialize;
+use std::fmt;
+
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
 
 use super::YahooError;
+use crate::async_impl::FinancialReport;
+use crate::utils::iso8601_date;
 
-time::serde::format_description!(iso8601_date, Date, "[year repr:full]-[month repr:numerical]-[day padding:zero]");
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "PascalCase"))]
 pub struct YFinancialsResponse {
+    #[serde(alias = "quote_time_series_store")]
     pub quote_time_series_store: YQuoteTimeSeriesStore
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
 pub struct YQuoteTimeSeriesStore {
+    #[serde(alias = "time_series")]
     pub time_series: Option<TimeSeries>
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
 pub struct TimeSeries {
+    #[serde(alias = "annual_basic_average_shares")]
     pub annual_basic_average_shares: Vec<Option<AnnualBasicAverageShares>>,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
 pub struct AnnualBasicAverageShares {
-    #[serde(with = "iso8601_date")]
+    #[serde(with = "iso8601_date", alias = "as_of_date")]
     pub as_of_date: time::Date,
+    #[serde(alias = "currency_code")]
     pub currency_code: String,
+    #[serde(alias = "reported_value")]
     pub reported_value: ReportedValue,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
 pub struct ReportedValue {
     pub raw: i64,
 }
 
 impl YFinancialsResponse {
     pub fn from_json(json: serde_json::Value) -> Result<YFinancialsResponse, YahooError> {
-        serde_json::from_value(json).map_err(|e| YahooError::DeserializeFailed(e.to_string()))
+        serde_json::from_value(json).map_err(YahooError::from)
+    }
+
+    /// Build the response from the annual basic average shares of the fundamentals timeseries API
+    pub fn from_timeseries(response: YTimeseriesResponse) -> YFinancialsResponse {
+        let annual_basic_average_shares = response
+            .timeseries
+            .result
+            .into_iter()
+            .filter(|result| result.key() == Some("annualBasicAverageShares"))
+            .flat_map(|result| result.values)
+            .map(|value| {
+                value.map(|value| AnnualBasicAverageShares {
+                    as_of_date: value.as_of_date,
+                    currency_code: value.currency_code.unwrap_or_default(),
+                    reported_value: ReportedValue {
+                        raw: value.reported_value.raw as i64,
+                    },
+                })
+            })
+            .collect();
+        YFinancialsResponse {
+            quote_time_series_store: YQuoteTimeSeriesStore {
+                time_series: Some(TimeSeries {
+                    annual_basic_average_shares,
+                }),
+            },
+        }
     }
 
     pub fn shares_on_issue(&self) -> Option<i64> {
@@ -47,3 +84,223 @@ impl YFinancialsResponse {
             .and_then(|s| s.as_ref().map(|s| s.reported_value.raw)))
     }
 }
+
+/// Response of the fundamentals timeseries endpoint
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YTimeseriesResponse {
+    pub timeseries: YTimeseries,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YTimeseries {
+    #[serde(default)]
+    pub result: Vec<YTimeseriesResult>,
+}
+
+/// A single requested type (e.g. `annualBasicAverageShares`) of the timeseries response
+#[derive(Debug)]
+pub struct YTimeseriesResult {
+    pub meta: YTimeseriesMeta,
+    pub values: Vec<Option<YTimeseriesValue>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YTimeseriesMeta {
+    #[serde(rename(deserialize = "type"), alias = "types")]
+    pub types: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
+pub struct YTimeseriesValue {
+    #[serde(with = "iso8601_date", alias = "as_of_date")]
+    pub as_of_date: time::Date,
+    #[serde(default, alias = "currency_code")]
+    pub currency_code: Option<String>,
+    #[serde(alias = "reported_value")]
+    pub reported_value: YTimeseriesReportedValue,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YTimeseriesReportedValue {
+    pub raw: f64,
+}
+
+impl YTimeseriesResult {
+    /// Name of the type, e.g. `annualBasicAverageShares`
+    pub fn key(&self) -> Option<&str> {
+        self.meta.types.first().map(|s| s.as_str())
+    }
+}
+
+struct YTimeseriesResultVisitor;
+
+impl<'de> Visitor<'de> for YTimeseriesResultVisitor {
+    type Value = YTimeseriesResult;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("timeseries result object")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut meta = None;
+        let mut values = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "meta" => meta = Some(map.next_value()?),
+                "timestamp" => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+                // the values are stored under the name of the requested type
+                _ => values = Some(map.next_value()?),
+            }
+        }
+        Ok(YTimeseriesResult {
+            meta: meta.ok_or_else(|| de::Error::missing_field("meta"))?,
+            values: values.unwrap_or_default(),
+        })
+    }
+}
+
+impl Serialize for YTimeseriesResult {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("meta", &self.meta)?;
+        map.serialize_entry(self.key().unwrap_or("values"), &self.values)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for YTimeseriesResult {
+    fn deserialize<D>(deserializer: D) -> Result<YTimeseriesResult, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(YTimeseriesResultVisitor)
+    }
+}
+
+impl YTimeseriesResponse {
+    pub fn from_json(json: serde_json::Value) -> Result<YTimeseriesResponse, YahooError> {
+        serde_json::from_value(json).map_err(YahooError::from)
+    }
+
+    /// Deserialize the response directly from the bytes of the body, without an
+    /// intermediate `serde_json::Value`
+    pub fn from_slice(body: &[u8]) -> Result<YTimeseriesResponse, YahooError> {
+        serde_json::from_slice(body).map_err(YahooError::from)
+    }
+}
+
+/// Number of shares of a company as reported at a given date
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SharesOutstanding {
+    pub date: time::Date,
+    /// Weighted average of basic shares during the reporting period
+    pub basic_average_shares: Option<f64>,
+    /// Weighted average of diluted shares during the reporting period
+    pub diluted_average_shares: Option<f64>,
+    /// Number of ordinary shares outstanding at the end of the reporting period
+    pub ordinary_shares_number: Option<f64>,
+}
+
+impl SharesOutstanding {
+    /// Share counts of a financial report as of the given date
+    pub(crate) fn from_report(date: time::Date, report: &FinancialReport) -> SharesOutstanding {
+        SharesOutstanding {
+            date,
+            basic_average_shares: report.basic_average_shares,
+            diluted_average_shares: report.diluted_average_shares,
+            ordinary_shares_number: report.ordinary_shares_number,
+        }
+    }
+
+    /// Best available share count, preferring the number of ordinary shares
+    /// at the reporting date over the period averages
+    pub fn shares(&self) -> Option<f64> {
+        self.ordinary_shares_number
+            .or(self.basic_average_shares)
+            .or(self.diluted_average_shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+    use crate::utils::tests::assert_serde_round_trip;
+
+    #[test]
+    fn test_deserialize_timeseries() {
+        let json = serde_json::json!({
+            "timeseries": {
+                "result": [{
+                    "meta": { "symbol": ["AAPL"], "type": ["annualBasicAverageShares"] },
+                    "timestamp": [1601424000, 1632960000],
+                    "annualBasicAverageShares": [
+                        null,
+                        {
+                            "dataId": 12001,
+                            "asOfDate": "2021-09-30",
+                            "periodType": "12M",
+                            "currencyCode": "USD",
+                            "reportedValue": { "raw": 16701272000u64, "fmt": "16.70B" }
+                        }
+                    ]
+                }, {
+                    "meta": { "symbol": ["AAPL"], "type": ["annualOrdinarySharesNumber"] }
+                }],
+                "error": null
+            }
+        });
+        let response = YTimeseriesResponse::from_slice(json.to_string().as_bytes()).unwrap();
+        let result = &response.timeseries.result;
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].key(), Some("annualBasicAverageShares"));
+        assert!(result[0].values[0].is_none());
+        let value = result[0].values[1].as_ref().unwrap();
+        assert_eq!(value.as_of_date, date!(2021 - 09 - 30));
+        assert_eq!(value.reported_value.raw, 16701272000.0);
+        assert!(result[1].values.is_empty());
+    }
+
+    #[test]
+    fn test_financials_from_timeseries() {
+        let json = serde_json::json!({
+            "timeseries": {
+                "result": [{
+                    "meta": { "symbol": ["AAPL"], "type": ["annualBasicAverageShares"] },
+                    "timestamp": [1601424000, 1632960000],
+                    "annualBasicAverageShares": [{
+                        "asOfDate": "2020-09-30",
+                        "currencyCode": "USD",
+                        "reportedValue": { "raw": 17352119000u64, "fmt": "17.35B" }
+                    }, {
+                        "asOfDate": "2021-09-30",
+                        "currencyCode": "USD",
+                        "reportedValue": { "raw": 16701272000u64, "fmt": "16.70B" }
+                    }]
+                }]
+            }
+        });
+        let response = YTimeseriesResponse::from_json(json).unwrap();
+        assert_serde_round_trip(&response);
+        let financials = YFinancialsResponse::from_timeseries(response);
+        assert_eq!(financials.shares_on_issue(), Some(16701272000));
+        assert_serde_round_trip(&financials);
+
+        let report = FinancialReport {
+            basic_average_shares: Some(16701272000.0),
+            ..FinancialReport::default()
+        };
+        assert_serde_round_trip(&report);
+        assert_serde_round_trip(&SharesOutstanding::from_report(date!(2021 - 09 - 30), &report));
+    }
+}
@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::statistics::KeyStatistics;
+use crate::utils::{DecimalValue, IntegerValue, OffsetDateTimeValue};
+
+/// Fund related modules of the quote summary, as returned for ETFs and mutual funds.
+/// Modules yahoo! does not provide for the quote are `None`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct YFundData {
+    #[serde(alias = "fund_profile")]
+    pub fund_profile: Option<FundProfile>,
+    #[serde(alias = "top_holdings")]
+    pub top_holdings: Option<TopHoldings>,
+    #[serde(alias = "fund_performance")]
+    pub fund_performance: Option<FundPerformance>,
+    #[serde(alias = "default_key_statistics")]
+    pub default_key_statistics: Option<KeyStatistics>,
+}
+
+impl YFundData {
+    /// The date the fund has been launched
+    pub fn inception_date(&self) -> Option<OffsetDateTime> {
+        self.default_key_statistics
+            .as_ref()
+            .and_then(|s| s.fund_inception_date.as_ref())
+            .and_then(|d| d.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct FundProfile {
+    pub family: Option<String>,
+    #[serde(alias = "category_name")]
+    pub category_name: Option<String>,
+    #[serde(alias = "legal_type")]
+    pub legal_type: Option<String>,
+    #[serde(alias = "style_box_url")]
+    pub style_box_url: Option<String>,
+    #[serde(alias = "management_info")]
+    pub management_info: Option<FundManagementInfo>,
+    #[serde(alias = "fees_expenses_investment")]
+    pub fees_expenses_investment: Option<FundFees>,
+    /// Average fees of the fund category
+    #[serde(alias = "fees_expenses_investment_cat")]
+    pub fees_expenses_investment_cat: Option<FundFees>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct FundManagementInfo {
+    #[serde(alias = "manager_name")]
+    pub manager_name: Option<String>,
+    #[serde(alias = "manager_bio")]
+    pub manager_bio: Option<String>,
+    #[serde(rename(deserialize = "startdate"), alias = "start_date")]
+    pub start_date: Option<OffsetDateTimeValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct FundFees {
+    #[serde(alias = "annual_report_expense_ratio")]
+    pub annual_report_expense_ratio: Option<DecimalValue>,
+    #[serde(alias = "net_exp_ratio")]
+    pub net_exp_ratio: Option<DecimalValue>,
+    #[serde(alias = "gross_exp_ratio")]
+    pub gross_exp_ratio: Option<DecimalValue>,
+    #[serde(alias = "annual_holdings_turnover")]
+    pub annual_holdings_turnover: Option<DecimalValue>,
+    #[serde(alias = "total_net_assets")]
+    pub total_net_assets: Option<DecimalValue>,
+    #[serde(alias = "front_end")]
+    pub front_end: Option<DecimalValue>,
+    #[serde(alias = "deferred_sales_load")]
+    pub deferred_sales_load: Option<DecimalValue>,
+    #[serde(alias = "twelve_b_one")]
+    pub twelve_b_one: Option<DecimalValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct TopHoldings {
+    #[serde(alias = "cash_position")]
+    pub cash_position: Option<DecimalValue>,
+    #[serde(alias = "stock_position")]
+    pub stock_position: Option<DecimalValue>,
+    #[serde(alias = "bond_position")]
+    pub bond_position: Option<DecimalValue>,
+    #[serde(alias = "preferred_position")]
+    pub preferred_position: Option<DecimalValue>,
+    #[serde(alias = "convertible_position")]
+    pub convertible_position: Option<DecimalValue>,
+    #[serde(alias = "other_position")]
+    pub other_position: Option<DecimalValue>,
+    pub holdings: Vec<FundHolding>,
+    #[serde(alias = "equity_holdings")]
+    pub equity_holdings: Option<EquityHoldings>,
+    #[serde(alias = "bond_holdings")]
+    pub bond_holdings: Option<BondHoldings>,
+    /// List of single entry maps from rating (e.g. `aaa`) to its weight
+    #[serde(alias = "bond_ratings")]
+    pub bond_ratings: Vec<HashMap<String, DecimalValue>>,
+    /// List of single entry maps from sector (e.g. `technology`) to its weight
+    #[serde(alias = "sector_weightings")]
+    pub sector_weightings: Vec<HashMap<String, DecimalValue>>,
+}
+
+impl TopHoldings {
+    /// Weight of each sector in the fund
+    pub fn sector_weights(&self) -> HashMap<String, Decimal> {
+        flatten_weights(&self.sector_weightings)
+    }
+
+    /// Weight of each bond rating in the fund
+    pub fn bond_rating_weights(&self) -> HashMap<String, Decimal> {
+        flatten_weights(&self.bond_ratings)
+    }
+
+    /// Weight of each asset class (cash, stock, bond, preferred, convertible, other) in the fund
+    pub fn asset_class_weights(&self) -> HashMap<String, Decimal> {
+        [
+            ("cash", &self.cash_position),
+            ("stock", &self.stock_position),
+            ("bond", &self.bond_position),
+            ("preferred", &self.preferred_position),
+            ("convertible", &self.convertible_position),
+            ("other", &self.other_position),
+        ]
+        .iter()
+        .filter_map(|(name, weight)| {
+            weight
+                .as_ref()
+                .and_then(|w| w.0)
+                .map(|w| (name.to_string(), w))
+        })
+        .collect()
+    }
+}
+
+fn flatten_weights(weights: &[HashMap<String, DecimalValue>]) -> HashMap<String, Decimal> {
+    weights
+        .iter()
+        .flat_map(|w| w.iter())
+        .filter_map(|(name, weight)| weight.0.map(|w| (name.clone(), w)))
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct FundHolding {
+    pub symbol: Option<String>,
+    #[serde(alias = "holding_name")]
+    pub holding_name: Option<String>,
+    #[serde(alias = "holding_percent")]
+    pub holding_percent: Option<DecimalValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct EquityHoldings {
+    #[serde(alias = "price_to_earnings")]
+    pub price_to_earnings: Option<DecimalValue>,
+    #[serde(alias = "price_to_book")]
+    pub price_to_book: Option<DecimalValue>,
+    #[serde(alias = "price_to_sales")]
+    pub price_to_sales: Option<DecimalValue>,
+    #[serde(alias = "price_to_cashflow")]
+    pub price_to_cashflow: Option<DecimalValue>,
+    #[serde(alias = "median_market_cap")]
+    pub median_market_cap: Option<DecimalValue>,
+    #[serde(alias = "three_year_earnings_growth")]
+    pub three_year_earnings_growth: Option<DecimalValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct BondHoldings {
+    pub maturity: Option<DecimalValue>,
+    pub duration: Option<DecimalValue>,
+    #[serde(alias = "credit_quality")]
+    pub credit_quality: Option<DecimalValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct FundPerformance {
+    #[serde(alias = "fund_category_name")]
+    pub fund_category_name: Option<String>,
+    #[serde(alias = "performance_overview")]
+    pub performance_overview: Option<PerformanceOverview>,
+    #[serde(alias = "trailing_returns")]
+    pub trailing_returns: Option<TrailingReturns>,
+    /// Trailing returns based on the net asset value
+    #[serde(alias = "trailing_returns_nav")]
+    pub trailing_returns_nav: Option<TrailingReturns>,
+    /// Trailing returns of the fund category
+    #[serde(alias = "trailing_returns_cat")]
+    pub trailing_returns_cat: Option<TrailingReturns>,
+    #[serde(alias = "annual_total_returns")]
+    pub annual_total_returns: Option<AnnualTotalReturns>,
+    #[serde(alias = "past_quarterly_returns")]
+    pub past_quarterly_returns: Option<PastQuarterlyReturns>,
+    #[serde(alias = "risk_overview_statistics")]
+    pub risk_overview_statistics: Option<RiskOverviewStatistics>,
+    /// Risk statistics of the fund category
+    #[serde(alias = "risk_overview_statistics_cat")]
+    pub risk_overview_statistics_cat: Option<RiskOverviewStatistics>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct PerformanceOverview {
+    #[serde(alias = "as_of_date")]
+    pub as_of_date: Option<OffsetDateTimeValue>,
+    #[serde(alias = "ytd_return_pct")]
+    pub ytd_return_pct: Option<DecimalValue>,
+    #[serde(alias = "five_yr_avg_return_pct")]
+    pub five_yr_avg_return_pct: Option<DecimalValue>,
+    #[serde(alias = "morning_star_return_rating")]
+    pub morning_star_return_rating: Option<IntegerValue>,
+    #[serde(alias = "num_years_up")]
+    pub num_years_up: Option<IntegerValue>,
+    #[serde(alias = "num_years_down")]
+    pub num_years_down: Option<IntegerValue>,
+    #[serde(alias = "best_one_yr_total_return")]
+    pub best_one_yr_total_return: Option<DecimalValue>,
+    #[serde(alias = "worst_one_yr_total_return")]
+    pub worst_one_yr_total_return: Option<DecimalValue>,
+    #[serde(alias = "best_three_yr_total_return")]
+    pub best_three_yr_total_return: Option<DecimalValue>,
+    #[serde(alias = "worst_three_yr_total_return")]
+    pub worst_three_yr_total_return: Option<DecimalValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct TrailingReturns {
+    #[serde(alias = "as_of_date")]
+    pub as_of_date: Option<OffsetDateTimeValue>,
+    pub ytd: Option<DecimalValue>,
+    #[serde(alias = "one_month")]
+    pub one_month: Option<DecimalValue>,
+    #[serde(alias = "three_month")]
+    pub three_month: Option<DecimalValue>,
+    #[serde(alias = "one_year")]
+    pub one_year: Option<DecimalValue>,
+    #[serde(alias = "three_year")]
+    pub three_year: Option<DecimalValue>,
+    #[serde(alias = "five_year")]
+    pub five_year: Option<DecimalValue>,
+    #[serde(alias = "ten_year")]
+    pub ten_year: Option<DecimalValue>,
+    #[serde(alias = "last_bull_mkt")]
+    pub last_bull_mkt: Option<DecimalValue>,
+    #[serde(alias = "last_bear_mkt")]
+    pub last_bear_mkt: Option<DecimalValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct AnnualTotalReturns {
+    pub returns: Vec<AnnualReturn>,
+    /// Annual returns of the fund category
+    #[serde(alias = "returns_cat")]
+    pub returns_cat: Vec<AnnualReturn>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct AnnualReturn {
+    pub year: Option<String>,
+    #[serde(alias = "annual_value")]
+    pub annual_value: Option<DecimalValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct PastQuarterlyReturns {
+    pub returns: Vec<QuarterlyReturns>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct QuarterlyReturns {
+    pub year: Option<String>,
+    pub q1: Option<DecimalValue>,
+    pub q2: Option<DecimalValue>,
+    pub q3: Option<DecimalValue>,
+    pub q4: Option<DecimalValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct RiskOverviewStatistics {
+    #[serde(alias = "risk_statistics")]
+    pub risk_statistics: Vec<RiskStatistics>,
+}
+
+/// Risk statistics over a given period, e.g. `5y`
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub struct RiskStatistics {
+    pub year: Option<String>,
+    pub alpha: Option<DecimalValue>,
+    pub beta: Option<DecimalValue>,
+    #[serde(alias = "mean_annual_return")]
+    pub mean_annual_return: Option<DecimalValue>,
+    #[serde(alias = "r_squared")]
+    pub r_squared: Option<DecimalValue>,
+    #[serde(alias = "std_dev")]
+    pub std_dev: Option<DecimalValue>,
+    #[serde(alias = "sharpe_ratio")]
+    pub sharpe_ratio: Option<DecimalValue>,
+    #[serde(alias = "treynor_ratio")]
+    pub treynor_ratio: Option<DecimalValue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quote_summary::YQuoteSummaryResponse;
+    use crate::utils::tests::assert_serde_round_trip;
+
+    #[test]
+    fn test_deserialize_fund_data() {
+        let json = serde_json::json!({
+            "quoteSummary": {
+                "result": [{
+                    "fundProfile": {
+                        "family": "Vanguard",
+                        "categoryName": "Large Blend",
+                        "legalType": null,
+                        "feesExpensesInvestment": {
+                            "annualReportExpenseRatio": { "raw": 0.0004, "fmt": "0.04%" },
+                            "annualHoldingsTurnover": { "raw": 0.08, "fmt": "8.00%" },
+                            "totalNetAssets": { "raw": 1241890.5, "fmt": "1.24M" }
+                        }
+                    },
+                    "topHoldings": {
+                        "stockPosition": { "raw": 0.9962, "fmt": "99.62%" },
+                        "cashPosition": { "raw": 0.0038, "fmt": "0.38%" },
+                        "holdings": [{
+                            "symbol": "AAPL",
+                            "holdingName": "Apple Inc",
+                            "holdingPercent": { "raw": 0.0596, "fmt": "5.96%" }
+                        }],
+                        "bondRatings": [{ "bb": { "raw": 0, "fmt": "0.00%" } }],
+                        "sectorWeightings": [
+                            { "realestate": { "raw": 0.0338, "fmt": "3.38%" } },
+                            { "technology": { "raw": 0.2513, "fmt": "25.13%" } }
+                        ]
+                    },
+                    "fundPerformance": {
+                        "trailingReturns": {
+                            "asOfDate": { "raw": 1666915200, "fmt": "2022-10-28" },
+                            "oneYear": { "raw": -0.1512, "fmt": "-15.12%" }
+                        },
+                        "annualTotalReturns": {
+                            "returns": [{ "year": "2021", "annualValue": { "raw": 0.2571, "fmt": "25.71" } }],
+                            "returnsCat": []
+                        },
+                        "riskOverviewStatistics": {
+                            "riskStatistics": [{ "year": "5y", "sharpeRatio": { "raw": 0.52, "fmt": "0.52" } }]
+                        }
+                    },
+                    "defaultKeyStatistics": {
+                        "fundInceptionDate": { "raw": 974332800, "fmt": "2000-11-13" }
+                    }
+                }],
+                "error": null
+            }
+        });
+        let data = YQuoteSummaryResponse::<YFundData>::from_slice(json.to_string().as_bytes())
+            .unwrap()
+            .into_result()
+            .unwrap();
+        assert_serde_round_trip(&data);
+        assert!(data.inception_date().is_some());
+        let profile = data.fund_profile.unwrap();
+        assert_eq!(profile.family.as_deref(), Some("Vanguard"));
+        assert!(profile.fees_expenses_investment.unwrap().annual_report_expense_ratio.unwrap().0.is_some());
+        let holdings = data.top_holdings.unwrap();
+        assert_eq!(holdings.holdings[0].symbol.as_deref(), Some("AAPL"));
+        assert_eq!(holdings.sector_weights().len(), 2);
+        assert_eq!(holdings.bond_rating_weights().len(), 1);
+        assert_eq!(holdings.asset_class_weights().len(), 2);
+        let performance = data.fund_performance.unwrap();
+        assert!(performance.trailing_returns.unwrap().one_year.unwrap().0.is_some());
+        assert_eq!(performance.annual_total_returns.unwrap().returns.len(), 1);
+        assert_eq!(performance.risk_overview_statistics.unwrap().risk_statistics[0].year.as_deref(), Some("5y"));
+    }
+
+    #[test]
+    fn test_serialize_fund_data() {
+        let json = serde_json::json!({
+            "fundProfile": {
+                "feesExpensesInvestment": {
+                    "annualReportExpenseRatio": { "raw": 0.0004, "fmt": "0.04%" }
+                }
+            },
+            "defaultKeyStatistics": {
+                "fundInceptionDate": { "raw": 974332800, "fmt": "2000-11-13" }
+            }
+        });
+        let data: YFundData = serde_json::from_value(json).unwrap();
+        let json = serde_json::to_value(&data).unwrap();
+        // wrapped values are serialized as plain values, with field names in snake case
+        assert_eq!(json["fund_profile"]["fees_expenses_investment"]["annual_report_expense_ratio"], "0.0004");
+        assert_eq!(json["default_key_statistics"]["fund_inception_date"], 974332800);
+
+        let cached: YFundData = serde_json::from_value(json).unwrap();
+        assert_eq!(cached.inception_date(), data.inception_date());
+        let fees = cached.fund_profile.unwrap().fees_expenses_investment.unwrap();
+        assert_eq!(fees.annual_report_expense_ratio.unwrap().0, Some(Decimal::new(4, 4)));
+    }
+}
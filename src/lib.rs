@@ -10,13 +10,16 @@
 //!
 //! Use the `blocking` feature to get the previous behavior back: i.e. `yahoo_finance_api = {"version": "1.0", features = ["blocking"]}`.
 //!
+//! Use the `arrow` feature to convert quote histories to Apache Arrow `RecordBatch`es and write them as Parquet files partitioned by symbol.
+//!
+//! Use the `polars` feature to convert quotes, dividends, splits, search results and fundamentals to polars `DataFrame`s.
+//!
 #![cfg_attr(
     not(feature = "blocking"),
     doc = "
 # Get the latest available quote:
 ```rust
 use yahoo_finance_api as yahoo;
-use time::OffsetDateTime;
 use tokio_test;
 
 fn main() {
@@ -26,8 +29,7 @@ fn main() {
     // extract just the latest valid quote summery
     // including timestamp,open,close,high,low,volume
     let quote = response.last_quote().unwrap();
-    let time = OffsetDateTime::from_unix_timestamp(quote.timestamp as i64).unwrap();
-    println!(\"At {:?} quote price of Apple was {}\", time, quote.close);
+    println!(\"At {:?} quote price of Apple was {}\", quote.datetime(), quote.close);
 }
 ```
 # Get history of quotes for given time period:
@@ -91,8 +93,6 @@ returning `None` if the field found missing in the response.
 # Get the latest available quote (with blocking feature enabled):
 ```rust
 use yahoo_finance_api as yahoo;
-use std::time::{Duration, UNIX_EPOCH};
-use time::OffsetDateTime;
 use tokio_test;
 
 fn main() {
@@ -102,9 +102,7 @@ fn main() {
     // extract just the latest valid quote summery
     // including timestamp,open,close,high,low,volume
     let quote = response.last_quote().unwrap();
-    let time: OffsetDateTime =
-        OffsetDateTime::from_unix_timestamp(quote.timestamp).unwrap();
-    println!(\"At {:?} quote price of Apple was {}\", time, quote.close);
+    println!(\"At {:?} quote price of Apple was {}\", quote.datetime(), quote.close);
 }
 ```
 //!
@@ -159,38 +157,100 @@ fn main() {
 ```
 "
 )]
+//!
+//! # Serialization
+//!
+//! All data types implement `serde::Serialize` and `serde::Deserialize`, e.g. to cache
+//! responses as JSON or pass them between processes. The serialized format is stable
+//! and is defined by the public fields of the types, not by yahoo!'s responses:
+//!
+//! * All types, including the `Y`-prefixed response types, use their field names in
+//!   snake case, e.g. `split_ratio` of [`Split`] or `exchange_name` of [`YMetaData`],
+//!   rather than yahoo!'s camel case names.
+//! * Timestamps are serialized as unix timestamps in seconds and dates as `YYYY-MM-DD`.
+//! * Decimal values are serialized as strings to keep their precision.
+//! * Values wrapped by yahoo! in an object with `raw` and `fmt` properties
+//!   (e.g. in [`YStatisticsResponse`] or [`YFundData`]) are serialized as plain values.
+//! * Missing values are serialized as `null`.
+//!
+//! The `Y`-prefixed response types deserialize both from yahoo!'s responses and from
+//! their serialized form, so cached responses can be read back with `serde` as well as
+//! with their `from_json` constructors. [`YahooError`] is not serializable, since it may
+//! contain the underlying error of the connection.
 
+mod adjustment;
+#[cfg(feature = "arrow")]
+mod arrow_io;
+mod corporate_actions;
+mod csv_io;
+mod decimal_quotes;
+#[cfg(feature = "polars")]
+mod polars_io;
+mod quote_series;
+mod quote_summary;
 mod quotes;
+mod resample;
 mod search_result;
+mod sec_filings;
 mod yahoo_error;
+mod esg;
 mod financials;
+mod fund;
 mod statistics;
+mod total_return;
 mod utils;
+mod valuation;
 
 use reqwest::StatusCode;
 
-pub use financials::YFinancialsResponse;
+pub use adjustment::{AdjustmentFactor, AdjustmentMode};
+#[cfg(feature = "arrow")]
+pub use arrow_io::{quote_schema, quotes_to_record_batch, write_parquet_by_symbol};
+pub use corporate_actions::{CorporateAction, CorporateActionKind};
+pub use csv_io::{read_quotes_csv, CsvColumn, CsvDateFormat, CsvEventOptions, CsvOptions};
+pub use decimal_quotes::{
+    DecimalAdjClose, DecimalQuoteBlock, DecimalQuoteList, YDecimalChart, YDecimalMetaData, YDecimalQuoteBlock,
+    YDecimalResponse,
+};
+#[cfg(feature = "polars")]
+pub use polars_io::{financial_reports_to_dataframe, shares_outstanding_to_dataframe};
+pub use esg::{EsgScores, PeerPerformance};
+pub use financials::{SharesOutstanding, YFinancialsResponse, YTimeseriesResponse};
+pub use fund::{
+    AnnualReturn, AnnualTotalReturns, BondHoldings, EquityHoldings, FundFees, FundHolding,
+    FundManagementInfo, FundPerformance, FundProfile, PastQuarterlyReturns, PerformanceOverview,
+    QuarterlyReturns, RiskOverviewStatistics, RiskStatistics, TopHoldings, TrailingReturns,
+    YFundData,
+};
 pub use statistics::YStatisticsResponse;
+pub use quote_series::QuoteSeries;
 pub use quotes::{
-    AdjClose, PeriodInfo, Quote, QuoteBlock, QuoteList, TradingPeriod, YChart, YMetaData,
-    YQuoteBlock, YResponse, Split, Dividend
+    AdjClose, DecimalQuote, MissingValuePolicy, PeriodInfo, Quote, QuoteBlock, QuoteList, QuoteOpt,
+    TradingPeriod, YChart, YChartError, YComparison, YMetaData, YQuoteBlock, YResponse, Split,
+    Dividend, CapitalGain
 };
+pub use resample::{BarLabel, ResampleInterval};
 pub use search_result::{YNewsItem, YQuoteItem, YQuoteItemOpt, YSearchResult, YSearchResultOpt};
-pub use yahoo_error::YahooError;
+pub use total_return::{total_return_index, TotalReturn, TotalReturnOptions};
+pub use valuation::{valuation_history, Valuation};
+pub use sec_filings::{SecFiling, SecFilingExhibit, SecFilingType};
+pub use yahoo_error::{RequestContext, YahooError};
 
 const YSCRAPE_URL: &str = "https://finance.yahoo.com/quote";
 const YCHART_URL: &str = "https://query1.finance.yahoo.com/v8/finance/chart";
 const YSEARCH_URL: &str = "https://query2.finance.yahoo.com/v1/finance/search";
+const YQUOTE_SUMMARY_URL: &str = "https://query2.finance.yahoo.com/v10/finance/quoteSummary";
+const YTIMESERIES_URL: &str = "https://query1.finance.yahoo.com/ws/fundamentals-timeseries/v1/finance/timeseries";
 
 // Macros instead of constants,
 macro_rules! YCHART_PERIOD_QUERY {
     () => {
-        "{url}/{symbol}?symbol={symbol}&period1={start}&period2={end}&interval={interval}&events=div|split"
+        "{url}/{symbol}?symbol={symbol}&period1={start}&period2={end}&interval={interval}&events=div|split|capitalGains"
     };
 }
 macro_rules! YCHART_RANGE_QUERY {
     () => {
-        "{url}/{symbol}?symbol={symbol}&interval={interval}&range={range}&events=div|split"
+        "{url}/{symbol}?symbol={symbol}&interval={interval}&range={range}&events=div|split|capitalGains"
     };
 }
 macro_rules! YFINANCIALS_QUERY {
@@ -203,6 +263,11 @@ macro_rules! YSTATISTICS_QUERY {
         "{url}/{symbol}/key-statistics"
     };
 }
+macro_rules! YQUOTE_SUMMARY_QUERY {
+    () => {
+        "{url}/{symbol}?modules={modules}"
+    };
+}
 macro_rules! YTICKER_QUERY {
     () => {
         "{url}?q={name}"
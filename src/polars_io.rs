@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use polars::prelude::{Column, DataFrame, Int32Chunked, Int64Chunked, IntoColumn, TimeUnit};
+use time::{Date, OffsetDateTime};
+
+use super::YahooError;
+use crate::async_impl::FinancialReport;
+use crate::financials::SharesOutstanding;
+use crate::quotes::YResponse;
+use crate::search_result::YSearchResult;
+
+/// Column of date and time values in UTC, given as unix timestamps
+fn datetime_column(name: &str, timestamps: &[i64]) -> Column {
+    let millis: Vec<i64> = timestamps.iter().map(|t| t * 1000).collect();
+    Int64Chunked::from_vec(name.into(), millis)
+        .into_datetime(TimeUnit::Milliseconds, Some("UTC".into()))
+        .into_column()
+}
+
+/// Column of dates
+fn date_column(name: &str, dates: &[Date]) -> Column {
+    let epoch = OffsetDateTime::UNIX_EPOCH.date();
+    let days: Vec<i32> = dates.iter().map(|date| (*date - epoch).whole_days() as i32).collect();
+    Int32Chunked::from_vec(name.into(), days).into_date().into_column()
+}
+
+/// Column repeating the same string, e.g. the symbol
+fn constant_column(name: &str, value: &str, len: usize) -> Column {
+    Column::new(name.into(), vec![value; len])
+}
+
+impl YResponse {
+    /// Meta data columns (`symbol` and `currency`) of the data frames of the response
+    fn meta_columns(&self, len: usize) -> Result<Vec<Column>, YahooError> {
+        let meta = &self.first_result()?.meta;
+        Ok(vec![
+            constant_column("symbol", &meta.symbol, len),
+            constant_column("currency", &meta.currency, len),
+        ])
+    }
+
+    /// Convert all quotes to a data frame with the columns `symbol`, `currency`,
+    /// `timestamp` (UTC), `open`, `high`, `low`, `close`, `adjclose` and `volume`.
+    /// Missing values are null, e.g. `adjclose` of intraday quotes.
+    pub fn to_dataframe(&self) -> Result<DataFrame, YahooError> {
+        let series = self.quote_series()?;
+        let adjclose = match series.adjclose() {
+            Some(adjclose) => adjclose.to_vec(),
+            None => vec![None; series.len()],
+        };
+        let mut columns = self.meta_columns(series.len())?;
+        columns.push(datetime_column("timestamp", series.timestamps()));
+        columns.push(Column::new("open".into(), series.open()));
+        columns.push(Column::new("high".into(), series.high()));
+        columns.push(Column::new("low".into(), series.low()));
+        columns.push(Column::new("close".into(), series.close()));
+        columns.push(Column::new("adjclose".into(), adjclose));
+        columns.push(Column::new("volume".into(), series.volume()));
+        DataFrame::new(columns).map_err(dataframe_failed)
+    }
+
+    /// Convert the dividends to a data frame with the columns `symbol`, `currency`,
+    /// `date` (the ex-dividend date, UTC) and `amount`
+    pub fn dividends_dataframe(&self) -> Result<DataFrame, YahooError> {
+        let dividends = self.dividends()?;
+        let dates: Vec<i64> = dividends.iter().map(|dividend| dividend.date).collect();
+        let mut columns = self.meta_columns(dividends.len())?;
+        columns.push(datetime_column("date", &dates));
+        columns.push(Column::new("amount".into(), dividends.iter().map(|d| d.amount).collect::<Vec<f64>>()));
+        DataFrame::new(columns).map_err(dataframe_failed)
+    }
+
+    /// Convert the splits to a data frame with the columns `symbol`, `currency`,
+    /// `date` (UTC), `numerator`, `denominator` and `split_ratio`
+    pub fn splits_dataframe(&self) -> Result<DataFrame, YahooError> {
+        let splits = self.splits()?;
+        let dates: Vec<i64> = splits.iter().map(|split| split.date).collect();
+        let mut columns = self.meta_columns(splits.len())?;
+        columns.push(datetime_column("date", &dates));
+        columns.push(Column::new("numerator".into(), splits.iter().map(|s| s.numerator).collect::<Vec<u64>>()));
+        columns.push(Column::new("denominator".into(), splits.iter().map(|s| s.denominator).collect::<Vec<u64>>()));
+        columns.push(Column::new(
+            "split_ratio".into(),
+            splits.iter().map(|s| s.split_ratio.as_str()).collect::<Vec<&str>>(),
+        ));
+        DataFrame::new(columns).map_err(dataframe_failed)
+    }
+}
+
+impl YSearchResult {
+    /// Convert the found quotes to a data frame with the columns `symbol`, `short_name`,
+    /// `long_name`, `exchange`, `quote_type`, `type_display`, `index`, `score` and `is_yahoo_finance`
+    pub fn to_dataframe(&self) -> Result<DataFrame, YahooError> {
+        let strings = |name: &str, value: fn(&crate::YQuoteItem) -> &str| {
+            Column::new(name.into(), self.quotes.iter().map(value).collect::<Vec<&str>>())
+        };
+        DataFrame::new(vec![
+            strings("symbol", |q| &q.symbol),
+            strings("short_name", |q| &q.short_name),
+            strings("long_name", |q| &q.long_name),
+            strings("exchange", |q| &q.exchange),
+            strings("quote_type", |q| &q.quote_type),
+            strings("type_display", |q| &q.type_display),
+            strings("index", |q| &q.index),
+            Column::new("score".into(), self.quotes.iter().map(|q| q.score).collect::<Vec<f64>>()),
+            Column::new(
+                "is_yahoo_finance".into(),
+                self.quotes.iter().map(|q| q.is_yahoo_finance).collect::<Vec<bool>>(),
+            ),
+        ])
+        .map_err(dataframe_failed)
+    }
+}
+
+/// Convert financial reports, e.g. as returned by
+/// [`YahooConnector::get_financials_timeseries`](crate::YahooConnector::get_financials_timeseries),
+/// to a data frame with one row per report date in ascending order
+pub fn financial_reports_to_dataframe(reports: &HashMap<Date, FinancialReport>) -> Result<DataFrame, YahooError> {
+    let mut reports: Vec<(&Date, &FinancialReport)> = reports.iter().collect();
+    reports.sort_by_key(|(date, _)| **date);
+    let dates: Vec<Date> = reports.iter().map(|(date, _)| **date).collect();
+    let values = |name: &str, value: fn(&FinancialReport) -> Option<f64>| {
+        Column::new(name.into(), reports.iter().map(|(_, r)| value(r)).collect::<Vec<Option<f64>>>())
+    };
+    DataFrame::new(vec![
+        date_column("date", &dates),
+        values("current_debt", |r| r.current_debt),
+        values("total_debt", |r| r.total_debt),
+        values("cash_and_cash_equivalents", |r| r.cash_and_cash_equivalents),
+        values("basic_average_shares", |r| r.basic_average_shares),
+        values("diluted_average_shares", |r| r.diluted_average_shares),
+        values("ordinary_shares_number", |r| r.ordinary_shares_number),
+    ])
+    .map_err(dataframe_failed)
+}
+
+/// Convert a history of shares outstanding, e.g. as returned by
+/// [`YahooConnector::get_shares_outstanding_history`](crate::YahooConnector::get_shares_outstanding_history),
+/// to a data frame
+pub fn shares_outstanding_to_dataframe(shares: &[SharesOutstanding]) -> Result<DataFrame, YahooError> {
+    let dates: Vec<Date> = shares.iter().map(|s| s.date).collect();
+    let values = |name: &str, value: fn(&SharesOutstanding) -> Option<f64>| {
+        Column::new(name.into(), shares.iter().map(value).collect::<Vec<Option<f64>>>())
+    };
+    DataFrame::new(vec![
+        date_column("date", &dates),
+        values("basic_average_shares", |s| s.basic_average_shares),
+        values("diluted_average_shares", |s| s.diluted_average_shares),
+        values("ordinary_shares_number", |s| s.ordinary_shares_number),
+    ])
+    .map_err(dataframe_failed)
+}
+
+fn dataframe_failed<E>(source: E) -> YahooError
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    YahooError::DataFrameFailed {
+        source: source.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use polars::prelude::DataType;
+
+    use super::*;
+    use crate::quotes::tests::{chart_json, chart_json_without_adjclose};
+
+    #[test]
+    fn test_quotes_dataframe() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let df = response.to_dataframe().unwrap();
+        assert_eq!(df.shape(), (3, 9));
+        assert_eq!(
+            df.column("timestamp").unwrap().dtype(),
+            &DataType::Datetime(TimeUnit::Milliseconds, Some("UTC".into()))
+        );
+        assert_eq!(df.column("symbol").unwrap().str().unwrap().get(0), Some("IBM"));
+        assert_eq!(df.column("currency").unwrap().str().unwrap().get(2), Some("USD"));
+        assert_eq!(df.column("close").unwrap().f64().unwrap().get(0), Some(119.7));
+        // missing values are null rather than zero
+        assert_eq!(df.column("low").unwrap().f64().unwrap().get(1), None);
+        assert_eq!(df.column("volume").unwrap().u64().unwrap().get(1), None);
+        assert_eq!(df.column("adjclose").unwrap().null_count(), 0);
+
+        let df = YResponse::from_json(chart_json_without_adjclose()).unwrap().to_dataframe().unwrap();
+        assert_eq!(df.column("adjclose").unwrap().null_count(), 3);
+        assert_eq!(df.column("adjclose").unwrap().dtype(), &DataType::Float64);
+
+        let dividends = response.dividends_dataframe().unwrap();
+        assert_eq!(dividends.shape(), (1, 4));
+        assert_eq!(dividends.column("amount").unwrap().f64().unwrap().get(0), Some(1.63));
+        let splits = response.splits_dataframe().unwrap();
+        assert_eq!(splits.shape(), (1, 6));
+    }
+
+    #[test]
+    fn test_financial_reports_dataframe() {
+        let mut reports = HashMap::new();
+        reports.insert(
+            date!(2021 - 09 - 30),
+            FinancialReport {
+                total_debt: Some(2.0),
+                ..FinancialReport::default()
+            },
+        );
+        reports.insert(date!(2020 - 09 - 30), FinancialReport::default());
+        let df = financial_reports_to_dataframe(&reports).unwrap();
+        assert_eq!(df.shape(), (2, 7));
+        assert_eq!(df.column("date").unwrap().dtype(), &DataType::Date);
+        let total_debt = df.column("total_debt").unwrap().f64().unwrap();
+        assert_eq!(total_debt.get(0), None);
+        assert_eq!(total_debt.get(1), Some(2.0));
+    }
+}
@@ -0,0 +1,189 @@
+use std::ops::Range;
+
+use super::YahooError;
+use crate::quotes::{QuoteOpt, YQuoteBlock, YResponse};
+
+/// Columnar view of the quotes of a response. The columns are borrowed from the
+/// response without copying; missing values are `None`.
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteSeries<'a> {
+    timestamps: &'a [i64],
+    open: &'a [Option<f64>],
+    high: &'a [Option<f64>],
+    low: &'a [Option<f64>],
+    close: &'a [Option<f64>],
+    volume: &'a [Option<u64>],
+    adjclose: Option<&'a [Option<f64>]>,
+}
+
+impl<'a> QuoteSeries<'a> {
+    /// Number of quotes
+    pub fn len(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timestamps.is_empty()
+    }
+
+    /// Unix timestamps (seconds since the epoch, UTC) of the start of the periods
+    pub fn timestamps(&self) -> &'a [i64] {
+        self.timestamps
+    }
+
+    pub fn open(&self) -> &'a [Option<f64>] {
+        self.open
+    }
+
+    pub fn high(&self) -> &'a [Option<f64>] {
+        self.high
+    }
+
+    pub fn low(&self) -> &'a [Option<f64>] {
+        self.low
+    }
+
+    pub fn close(&self) -> &'a [Option<f64>] {
+        self.close
+    }
+
+    pub fn volume(&self) -> &'a [Option<u64>] {
+        self.volume
+    }
+
+    /// Adjusted close prices, if contained in the response (e.g. not for intraday quotes)
+    pub fn adjclose(&self) -> Option<&'a [Option<f64>]> {
+        self.adjclose
+    }
+
+    /// The i-th quote, or `None` if out of range
+    pub fn get(&self, i: usize) -> Option<QuoteOpt> {
+        let timestamp = *self.timestamps.get(i)?;
+        Some(QuoteOpt {
+            timestamp,
+            open: self.open[i],
+            high: self.high[i],
+            low: self.low[i],
+            volume: self.volume[i],
+            close: self.close[i],
+            adjclose: self.adjclose.and_then(|adjclose| adjclose[i]),
+        })
+    }
+
+    /// Iterate over the quotes in chronological order
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = QuoteOpt> + ExactSizeIterator + 'a {
+        let series = *self;
+        (0..self.len()).map(move |i| series.get(i).unwrap())
+    }
+
+    /// View of the quotes in the given range of indices.
+    ///
+    /// Panics if the range is out of bounds, like slicing.
+    pub fn slice(&self, range: Range<usize>) -> QuoteSeries<'a> {
+        QuoteSeries {
+            timestamps: &self.timestamps[range.clone()],
+            open: &self.open[range.clone()],
+            high: &self.high[range.clone()],
+            low: &self.low[range.clone()],
+            close: &self.close[range.clone()],
+            volume: &self.volume[range.clone()],
+            adjclose: self.adjclose.map(|adjclose| &adjclose[range]),
+        }
+    }
+
+    /// View of the quotes with timestamps in `start..end`, assuming the timestamps are ascending
+    pub fn between(&self, start: i64, end: i64) -> QuoteSeries<'a> {
+        let from = self.timestamps.partition_point(|t| *t < start);
+        let to = self.timestamps.partition_point(|t| *t < end).max(from);
+        self.slice(from..to)
+    }
+
+    /// Iterate over all overlapping windows of `size` consecutive quotes, like `slice::windows`.
+    ///
+    /// Panics if `size` is zero.
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = QuoteSeries<'a>> + 'a {
+        assert!(size > 0, "window size must be positive");
+        let series = *self;
+        (0..(self.len() + 1).saturating_sub(size)).map(move |i| series.slice(i..i + size))
+    }
+
+    /// Iterate over non-overlapping chunks of `size` consecutive quotes, like `slice::chunks`.
+    /// The last chunk may be shorter.
+    ///
+    /// Panics if `size` is zero.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = QuoteSeries<'a>> + 'a {
+        assert!(size > 0, "chunk size must be positive");
+        let series = *self;
+        (0..self.len())
+            .step_by(size)
+            .map(move |i| series.slice(i..(i + size).min(series.len())))
+    }
+}
+
+impl YResponse {
+    /// Columnar view of the quotes, borrowing the data of the response
+    pub fn quote_series(&self) -> Result<QuoteSeries<'_>, YahooError> {
+        self.first_result()?.quote_series()
+    }
+}
+
+impl YQuoteBlock {
+    /// Columnar view of the quotes, borrowing the data of the block
+    pub fn quote_series(&self) -> Result<QuoteSeries<'_>, YahooError> {
+        self.check_consistency()?;
+        let quote = &self.indicators.quote[0];
+        Ok(QuoteSeries {
+            timestamps: &self.timestamp,
+            open: &quote.open,
+            high: &quote.high,
+            low: &quote.low,
+            close: &quote.close,
+            volume: &quote.volume,
+            adjclose: self
+                .indicators
+                .adjclose
+                .as_ref()
+                .map(|adjclose| adjclose[0].adjclose.as_slice()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quotes::tests::chart_json;
+
+    #[test]
+    fn test_quote_series() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let series = response.quote_series().unwrap();
+        assert_eq!(series.len(), 3);
+        assert_eq!(series.timestamps(), &[1593610200, 1593696600, 1593783000]);
+        assert_eq!(series.close(), &[Some(119.7), Some(121.1), Some(122.03)]);
+        assert_eq!(series.low()[1], None);
+        assert_eq!(series.adjclose().unwrap()[2], Some(114.4));
+
+        let quotes = response.quotes_opt(crate::MissingValuePolicy::Keep).unwrap();
+        assert_eq!(series.iter().collect::<Vec<_>>(), quotes);
+        assert_eq!(series.get(3), None);
+    }
+
+    #[test]
+    fn test_quote_series_windows() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let series = response.quote_series().unwrap();
+
+        let windows: Vec<_> = series.windows(2).collect();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[1].timestamps(), &[1593696600, 1593783000]);
+        assert_eq!(series.windows(4).count(), 0);
+
+        let chunks: Vec<_> = series.chunks(2).map(|chunk| chunk.len()).collect();
+        assert_eq!(chunks, vec![2, 1]);
+
+        let between = series.between(1593696600, 1593783000);
+        assert_eq!(between.timestamps(), &[1593696600]);
+        assert!(series.between(1593783001, 1593610200).is_empty());
+        assert_eq!(series.slice(1..3).open(), &[Some(121.2), Some(121.5)]);
+    }
+}
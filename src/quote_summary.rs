@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+use super::{RequestContext, YahooError};
+
+/// Response of the quote summary endpoint, containing the requested modules
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
+pub struct YQuoteSummaryResponse<T> {
+    #[serde(alias = "quote_summary")]
+    pub quote_summary: YQuoteSummary<T>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YQuoteSummary<T> {
+    pub result: Option<Vec<T>>,
+    pub error: Option<YQuoteSummaryError>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YQuoteSummaryError {
+    pub code: String,
+    pub description: String,
+}
+
+impl<T: for<'de> Deserialize<'de>> YQuoteSummaryResponse<T> {
+    /// Deserialize the response directly from the bytes of the body
+    pub fn from_slice(body: &[u8]) -> Result<YQuoteSummaryResponse<T>, YahooError> {
+        serde_json::from_slice(body).map_err(YahooError::from)
+    }
+
+    /// Return the modules of the first result
+    pub fn into_result(self) -> Result<T, YahooError> {
+        if let Some(error) = self.quote_summary.error {
+            return Err(YahooError::from_api_error(
+                &error.code,
+                &error.description,
+                RequestContext::default(),
+            ));
+        }
+        self.quote_summary
+            .result
+            .and_then(|result| result.into_iter().next())
+            .ok_or_else(YahooError::empty_data_set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::YQuoteSummaryStore;
+
+    #[test]
+    fn test_deserialize_statistics() {
+        let json = serde_json::json!({
+            "quoteSummary": {
+                "result": [{
+                    "defaultKeyStatistics": {
+                        "maxAge": 1,
+                        "enterpriseValue": { "raw": 2851740000000u64, "fmt": "2.85T", "longFmt": "2,851,740,000,000" },
+                        "priceToBook": { "raw": 45.74, "fmt": "45.74" },
+                        "mostRecentQuarter": { "raw": 1664496000, "fmt": "2022-09-24" },
+                        "sharesOutstanding": {},
+                        "forwardPE": { "raw": 23.88, "fmt": "23.88" },
+                        "52WeekChange": { "raw": -0.1743, "fmt": "-17.43%" },
+                        "shortRatio": { "raw": 1.23e0, "fmt": "1.23" },
+                        "beta": { "raw": "Infinity", "fmt": "" },
+                        "lastSplitFactor": "4:1",
+                        "lastSplitDate": { "raw": 1598832000, "fmt": "2020-08-31" },
+                        "category": null
+                    },
+                    "financialData": {
+                        "currentRatio": { "raw": 0.879, "fmt": "0.88" },
+                        "debtToEquity": { "raw": 261.446, "fmt": "261.45%" },
+                        "ebitda": { "raw": 130541002752u64, "fmt": "130.54B" },
+                        "financialCurrency": "USD",
+                        "freeCashflow": { "raw": 90215251968u64, "fmt": "90.22B" },
+                        "operatingCashflow": { "raw": 122151002112u64, "fmt": "122.15B" },
+                        "quickRatio": { "raw": 0.709, "fmt": "0.71" },
+                        "returnOnAssets": { "raw": 0.21214, "fmt": "21.21%" },
+                        "totalCash": { "raw": 48304001024u64, "fmt": "48.3B" },
+                        "totalDebt": { "raw": 132480000000u64, "fmt": "132.48B" },
+                        "totalRevenue": { "raw": 394328014848u64, "fmt": "394.33B" }
+                    }
+                }],
+                "error": null
+            }
+        });
+        let store = YQuoteSummaryResponse::<YQuoteSummaryStore>::from_slice(json.to_string().as_bytes())
+            .unwrap()
+            .into_result()
+            .unwrap();
+        let statistics = &store.default_key_statistics;
+        assert_eq!(statistics.enterprise_value.as_ref().unwrap().0, Some(2851740000000));
+        assert!(statistics.price_to_book.as_ref().unwrap().0.is_some());
+        assert!(statistics.most_recent_quarter.as_ref().unwrap().0.is_some());
+        assert_eq!(statistics.shares_outstanding.as_ref().unwrap().0, None);
+        assert!(statistics.forward_pe.as_ref().unwrap().0.is_some());
+        assert!(statistics.week_52_change.as_ref().unwrap().0.is_some());
+        assert!(statistics.short_ratio.as_ref().unwrap().0.is_some());
+        assert!(statistics.beta.as_ref().unwrap().0.is_none());
+        assert_eq!(statistics.last_split_factor.as_deref(), Some("4:1"));
+        assert!(statistics.last_split_date.as_ref().unwrap().0.is_some());
+        assert!(statistics.category.is_none());
+        let financial_data = store.financial_data.unwrap();
+        assert_eq!(financial_data.financial_currency.as_deref(), Some("USD"));
+        assert!(financial_data.debt_to_equity.is_some());
+        assert!(financial_data.target_mean_price.is_none());
+    }
+
+    #[test]
+    fn test_missing_statistics_modules() {
+        let json = serde_json::json!({
+            "quoteSummary": { "result": [{}], "error": null }
+        });
+        let store = YQuoteSummaryResponse::<YQuoteSummaryStore>::from_slice(json.to_string().as_bytes())
+            .unwrap()
+            .into_result()
+            .unwrap();
+        assert!(store.default_key_statistics.enterprise_value.is_none());
+        assert!(store.financial_data.is_none());
+    }
+
+    #[test]
+    fn test_quote_summary_error() {
+        let json = serde_json::json!({
+            "quoteSummary": {
+                "result": null,
+                "error": {
+                    "code": "Not Found",
+                    "description": "Quote not found for ticker symbol: XXXX"
+                }
+            }
+        });
+        let response = YQuoteSummaryResponse::<YQuoteSummaryStore>::from_slice(json.to_string().as_bytes()).unwrap();
+        assert!(matches!(
+            response.into_result(),
+            Err(YahooError::SymbolNotFound { symbol, .. }) if symbol == "XXXX"
+        ));
+    }
+}
@@ -1,65 +1,295 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::{Date, OffsetDateTime, UtcOffset};
+use time_tz::{timezones, OffsetDateTimeExt, Tz};
 
-use super::YahooError;
+use super::{RequestContext, YahooError};
+use crate::utils::{
+    datetime_from_timestamp, decimal_from_f64, null_as_default, price_hint_decimals,
+};
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct YResponse {
     pub chart: YChart,
 }
 
 impl YResponse {
-    fn check_consistency(&self) -> Result<(), YahooError> {
-        for stock in &self.chart.result {
-            let n = stock.timestamp.len();
-            if n == 0 {
-                return Err(YahooError::EmptyDataSet);
-            }
-            let quote = &stock.indicators.quote[0];
-            if quote.open.len() != n
-                || quote.high.len() != n
-                || quote.low.len() != n
-                || quote.volume.len() != n
-                || quote.close.len() != n
-            {
-                return Err(YahooError::DataInconsistency);
-            }
-            if let Some(ref adjclose) = stock.indicators.adjclose {
-                if adjclose[0].adjclose.len() != n {
-                    return Err(YahooError::DataInconsistency);
-                }
-            }
+    pub fn from_json(json: serde_json::Value) -> Result<YResponse, YahooError> {
+        let response: YResponse = serde_json::from_value(json)
+            .map_err(YahooError::from)?;
+        response.check_error()
+    }
+
+    /// Deserialize the response directly from the bytes of the body, without an
+    /// intermediate `serde_json::Value`
+    pub fn from_slice(body: &[u8]) -> Result<YResponse, YahooError> {
+        let response: YResponse = serde_json::from_slice(body)
+            .map_err(YahooError::from)?;
+        response.check_error()
+    }
+
+    /// Turn an error object in the response into the matching error
+    fn check_error(self) -> Result<YResponse, YahooError> {
+        match &self.chart.error {
+            Some(error) => Err(YahooError::from_api_error(
+                &error.code,
+                &error.description,
+                RequestContext::default(),
+            )),
+            None => Ok(self),
         }
-        Ok(())
     }
 
-    pub fn from_json(json: serde_json::Value) -> Result<YResponse, YahooError> {
-        serde_json::from_value(json).map_err(|e| YahooError::DeserializeFailed(e.to_string()))
+    /// Iterate over all result blocks of the response
+    pub fn results(&self) -> std::slice::Iter<'_, YQuoteBlock> {
+        self.chart.result.iter()
+    }
+
+    /// The first result block, i.e. the one of the requested symbol.
+    /// Returns `EmptyDataSet` if the response contains no result.
+    pub fn first_result(&self) -> Result<&YQuoteBlock, YahooError> {
+        self.chart.result.first().ok_or_else(YahooError::empty_data_set)
+    }
+
+    /// The result block of the given symbol, if contained in the response
+    pub fn result(&self, symbol: &str) -> Option<&YQuoteBlock> {
+        self.results().find(|block| block.meta.symbol == symbol)
+    }
+
+    /// Return the latest valid quote
+    pub fn last_quote(&self) -> Result<Quote, YahooError> {
+        self.first_result()?.last_quote()
+    }
+
+    pub fn quotes(&self) -> Result<Vec<Quote>, YahooError> {
+        self.first_result()?.quotes()
+    }
+
+    /// Return all quotes with missing values as `None` instead of zero, handled
+    /// according to the given policy
+    pub fn quotes_opt(&self, policy: MissingValuePolicy) -> Result<Vec<QuoteOpt>, YahooError> {
+        self.first_result()?.quotes_opt(policy)
+    }
+
+    /// Return the latest valid quote with decimal prices, rounded to the
+    /// number of decimal places given by the `priceHint` of the response
+    pub fn last_decimal_quote(&self) -> Result<DecimalQuote, YahooError> {
+        self.first_result()?.last_decimal_quote()
+    }
+
+    /// Return the quotes with decimal prices, rounded to the number of
+    /// decimal places given by the `priceHint` of the response
+    pub fn decimal_quotes(&self) -> Result<Vec<DecimalQuote>, YahooError> {
+        self.first_result()?.decimal_quotes()
+    }
+
+    /// This method retrieves information about the splits that might have
+    /// occured during the considered time period
+    pub fn splits(&self) -> Result<Vec<Split>, YahooError> {
+        self.first_result()?.splits()
+    }
+
+    /// This method retrieves information about the dividends that have
+    /// been recorded during the considered time period. 
+    ///
+    /// Note: Date is the ex-dividend date)
+    pub fn dividends(&self) -> Result<Vec<Dividend>, YahooError> {
+        self.first_result()?.dividends()
+    }
+
+    /// Capital gains distributed during the considered time period, e.g. by mutual funds,
+    /// dated by the ex-date
+    pub fn capital_gains(&self) -> Result<Vec<CapitalGain>, YahooError> {
+        self.first_result()?.capital_gains()
+    }
+}
+
+/// Struct for single quote
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, PartialOrd)]
+pub struct Quote {
+    /// Unix timestamp (seconds since the epoch, UTC) of the start of the period
+    pub timestamp: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub volume: u64,
+    pub close: f64,
+    pub adjclose: f64,
+}
+
+impl Quote {
+    /// Start of the period of the quote
+    pub fn datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.timestamp)
+    }
+
+    /// Start of the period of the quote in the local time of the exchange
+    pub fn exchange_datetime(&self, meta: &YMetaData) -> OffsetDateTime {
+        meta.to_exchange_time(self.timestamp)
+    }
+
+    /// Trading date of the quote, i.e. its date in the local time of the exchange
+    pub fn trading_date(&self, meta: &YMetaData) -> Date {
+        meta.trading_date(self.timestamp)
+    }
+}
+
+/// Struct for single quote, with missing values as `None`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct QuoteOpt {
+    /// Unix timestamp (seconds since the epoch, UTC) of the start of the period
+    pub timestamp: i64,
+    pub open: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub volume: Option<u64>,
+    pub close: Option<f64>,
+    pub adjclose: Option<f64>,
+}
+
+impl QuoteOpt {
+    /// Check if none of open, high, low, volume and close is missing. The adjusted close
+    /// is not checked, since yahoo! doesn't provide it for intraday quotes.
+    pub fn is_complete(&self) -> bool {
+        self.open.is_some()
+            && self.high.is_some()
+            && self.low.is_some()
+            && self.volume.is_some()
+            && self.close.is_some()
+    }
+
+    /// Start of the period of the quote
+    pub fn datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.timestamp)
+    }
+}
+
+/// Policy how to treat missing values in quotes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingValuePolicy {
+    /// Drop quotes with any missing value
+    Drop,
+    /// Replace missing prices by the last known value of the same field. Volumes
+    /// and values before the first known value remain `None`.
+    ForwardFill,
+    /// Keep missing values as `None`
+    Keep,
+}
+
+/// Struct for single quote with decimal prices. Only quotes with a close are
+/// represented, any other missing value is `None` rather than zero.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, PartialOrd)]
+pub struct DecimalQuote {
+    /// Unix timestamp (seconds since the epoch, UTC) of the start of the period
+    pub timestamp: i64,
+    pub open: Option<Decimal>,
+    pub high: Option<Decimal>,
+    pub low: Option<Decimal>,
+    pub volume: Option<u64>,
+    pub close: Decimal,
+    pub adjclose: Option<Decimal>,
+}
+
+impl DecimalQuote {
+    /// Convert the prices of a quote to decimals rounded to the given number of decimal places.
+    /// Returns `DataInconsistency` if the close is missing or a price can't be represented
+    /// as decimal.
+    pub fn from_quote(quote: &QuoteOpt, decimals: u32) -> Result<DecimalQuote, YahooError> {
+        let decimal = |value: f64| decimal_from_f64(value, decimals).ok_or_else(YahooError::data_inconsistency);
+        let optional = |value: Option<f64>| value.map(decimal).transpose();
+        Ok(DecimalQuote {
+            timestamp: quote.timestamp,
+            open: optional(quote.open)?,
+            high: optional(quote.high)?,
+            low: optional(quote.low)?,
+            volume: quote.volume,
+            close: decimal(quote.close.ok_or_else(YahooError::data_inconsistency)?)?,
+            adjclose: optional(quote.adjclose)?,
+        })
+    }
+
+    /// Start of the period of the quote
+    pub fn datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.timestamp)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YChart {
+    #[serde(deserialize_with = "null_as_default")]
+    pub result: Vec<YQuoteBlock>,
+    pub error: Option<YChartError>,
+}
+
+/// Error reported by yahoo! finance in the chart response
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct YChartError {
+    pub code: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct YQuoteBlock {
+    pub meta: YMetaData,
+    pub timestamp: Vec<i64>,
+    pub events: Option<EventsBlock>,
+    pub indicators: QuoteBlock,
+    /// Prices of the symbols requested for comparison
+    #[serde(default)]
+    pub comparisons: Vec<YComparison>,
+}
+
+impl YQuoteBlock {
+    pub(crate) fn check_consistency(&self) -> Result<(), YahooError> {
+        let n = self.timestamp.len();
+        if n == 0 {
+            return Err(YahooError::empty_data_set());
+        }
+        let quote = self
+            .indicators
+            .quote
+            .first()
+            .ok_or_else(YahooError::data_inconsistency)?;
+        if quote.open.len() != n
+            || quote.high.len() != n
+            || quote.low.len() != n
+            || quote.volume.len() != n
+            || quote.close.len() != n
+        {
+            return Err(YahooError::data_inconsistency());
+        }
+        if let Some(ref adjclose) = self.indicators.adjclose {
+            if adjclose.first().is_none_or(|adjclose| adjclose.adjclose.len() != n) {
+                return Err(YahooError::data_inconsistency());
+            }
+        }
+        Ok(())
     }
 
     /// Return the latest valid quote
     pub fn last_quote(&self) -> Result<Quote, YahooError> {
         self.check_consistency()?;
-        let stock = &self.chart.result[0];
-        let n = stock.timestamp.len();
+        let n = self.timestamp.len();
         for i in (0..n).rev() {
-            let quote = stock.indicators.get_ith_quote(stock.timestamp[i], i);
+            let quote = self.indicators.get_ith_quote(self.timestamp[i], i);
             if quote.is_ok() {
                 return quote;
             }
         }
-        Err(YahooError::EmptyDataSet)
+        Err(YahooError::empty_data_set())
     }
 
     pub fn quotes(&self) -> Result<Vec<Quote>, YahooError> {
         self.check_consistency()?;
-        let stock = &self.chart.result[0];
         let mut quotes = Vec::new();
-        let n = stock.timestamp.len();
+        let n = self.timestamp.len();
         for i in 0..n {
-            let timestamp = stock.timestamp[i];
-            let quote = stock.indicators.get_ith_quote(timestamp, i);
+            let timestamp = self.timestamp[i];
+            let quote = self.indicators.get_ith_quote(timestamp, i);
             if let Ok(q) = quote {
                 quotes.push(q);
             }
@@ -67,12 +297,68 @@ impl YResponse {
         Ok(quotes)
     }
 
-    /// This method retrieves information about the splits that might have
-    /// occured during the considered time period
+    /// Return all quotes with missing values as `None` instead of zero, handled
+    /// according to the given policy. The adjusted close only counts as missing if the
+    /// block contains adjusted close prices at all.
+    pub fn quotes_opt(&self, policy: MissingValuePolicy) -> Result<Vec<QuoteOpt>, YahooError> {
+        self.check_consistency()?;
+        let has_adjclose = self.indicators.adjclose.is_some();
+        let quotes = self
+            .timestamp
+            .iter()
+            .enumerate()
+            .map(|(i, timestamp)| self.indicators.get_ith_quote_opt(*timestamp, i));
+        Ok(match policy {
+            MissingValuePolicy::Keep => quotes.collect(),
+            MissingValuePolicy::Drop => quotes
+                .filter(|q| q.is_complete() && (q.adjclose.is_some() || !has_adjclose))
+                .collect(),
+            MissingValuePolicy::ForwardFill => {
+                let mut last: Option<QuoteOpt> = None;
+                quotes
+                    .map(|mut quote| {
+                        if let Some(last) = &last {
+                            quote.open = quote.open.or(last.open);
+                            quote.high = quote.high.or(last.high);
+                            quote.low = quote.low.or(last.low);
+                            quote.close = quote.close.or(last.close);
+                            quote.adjclose = quote.adjclose.or(last.adjclose);
+                        }
+                        last = Some(quote);
+                        quote
+                    })
+                    .collect()
+            }
+        })
+    }
+
+    /// Return the latest valid quote with decimal prices, rounded to the
+    /// number of decimal places given by the `priceHint` of the block
+    pub fn last_decimal_quote(&self) -> Result<DecimalQuote, YahooError> {
+        let quote = self
+            .quotes_opt(MissingValuePolicy::Keep)?
+            .into_iter()
+            .rfind(|quote| quote.close.is_some())
+            .ok_or_else(YahooError::empty_data_set)?;
+        DecimalQuote::from_quote(&quote, price_hint_decimals(self.meta.price_hint))
+    }
+
+    /// Return the quotes with decimal prices, rounded to the number of
+    /// decimal places given by the `priceHint` of the block
+    pub fn decimal_quotes(&self) -> Result<Vec<DecimalQuote>, YahooError> {
+        let quotes = self.quotes_opt(MissingValuePolicy::Keep)?;
+        let decimals = price_hint_decimals(self.meta.price_hint);
+        quotes
+            .iter()
+            .filter(|quote| quote.close.is_some())
+            .map(|quote| DecimalQuote::from_quote(quote, decimals))
+            .collect()
+    }
+
+    /// Splits that occured during the considered time period
     pub fn splits(&self) -> Result<Vec<Split>, YahooError> {
         self.check_consistency()?;
-        let stock = &self.chart.result[0];
-        if let Some(events) = &stock.events {
+        if let Some(events) = &self.events {
             if let Some(splits) = &events.splits {
                 let mut data = splits.values().cloned().collect::<Vec<Split>>();
                 data.sort_unstable_by_key(|d| d.date);
@@ -81,14 +367,11 @@ impl YResponse {
         }
         Ok(vec![])
     }
-    /// This method retrieves information about the dividends that have
-    /// been recorded during the considered time period. 
-    ///
-    /// Note: Date is the ex-dividend date)
+
+    /// Dividends recorded during the considered time period, dated by the ex-dividend date
     pub fn dividends(&self) -> Result<Vec<Dividend>, YahooError> {
         self.check_consistency()?;
-        let stock = &self.chart.result[0];
-        if let Some(events) = &stock.events {
+        if let Some(events) = &self.events {
             if let Some(dividends) = &events.dividends {
                 let mut data = dividends.values().cloned().collect::<Vec<Dividend>>();
                 data.sort_unstable_by_key(|d| d.date);
@@ -97,85 +380,182 @@ impl YResponse {
         }
         Ok(vec![])
     }
-}
 
-/// Struct for single quote
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub struct Quote {
-    pub timestamp: u64,
-    pub open: f64,
-    pub high: f64,
-    pub low: f64,
-    pub volume: u64,
-    pub close: f64,
-    pub adjclose: f64,
-}
+    /// Capital gains distributed during the considered time period, dated by the ex-date
+    pub fn capital_gains(&self) -> Result<Vec<CapitalGain>, YahooError> {
+        self.check_consistency()?;
+        if let Some(events) = &self.events {
+            if let Some(capital_gains) = &events.capital_gains {
+                let mut data = capital_gains.values().cloned().collect::<Vec<CapitalGain>>();
+                data.sort_unstable_by_key(|d| d.date);
+                return Ok(data);
+            }
+        }
+        Ok(vec![])
+    }
 
-#[derive(Deserialize, Debug)]
-pub struct YChart {
-    pub result: Vec<YQuoteBlock>,
-    pub error: Option<String>,
+    /// Prices of the given symbol requested for comparison, if contained in the block
+    pub fn comparison(&self, symbol: &str) -> Option<&YComparison> {
+        self.comparisons.iter().find(|c| c.symbol == symbol)
+    }
 }
 
-#[derive(Deserialize, Debug)]
-pub struct YQuoteBlock {
-    pub meta: YMetaData,
-    pub timestamp: Vec<u64>,
-    pub events: Option<EventsBlock>,
-    pub indicators: QuoteBlock,
+/// Prices of a symbol requested for comparison, aligned with the timestamps of the
+/// result block containing it
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
+pub struct YComparison {
+    pub symbol: String,
+    #[serde(default, alias = "chart_previous_close")]
+    pub chart_previous_close: Option<f64>,
+    #[serde(default)]
+    pub open: Vec<Option<f64>>,
+    #[serde(default)]
+    pub high: Vec<Option<f64>>,
+    #[serde(default)]
+    pub low: Vec<Option<f64>>,
+    #[serde(default)]
+    pub close: Vec<Option<f64>>,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
 pub struct YMetaData {
     pub currency: String,
     pub symbol: String,
+    #[serde(alias = "exchange_name")]
     pub exchange_name: String,
+    #[serde(alias = "instrument_type")]
     pub instrument_type: String,
-    pub first_trade_date: i32,
-    pub regular_market_time: u32,
+    #[serde(alias = "first_trade_date")]
+    pub first_trade_date: i64,
+    #[serde(alias = "regular_market_time")]
+    pub regular_market_time: i64,
     pub gmtoffset: i32,
     pub timezone: String,
+    #[serde(alias = "exchange_timezone_name")]
     pub exchange_timezone_name: String,
+    #[serde(alias = "regular_market_price")]
     pub regular_market_price: f64,
+    #[serde(alias = "chart_previous_close")]
     pub chart_previous_close: f64,
-    #[serde(default)]
+    #[serde(default, alias = "previous_close")]
     pub previous_close: Option<f64>,
     #[serde(default)]
     pub scale: Option<i32>,
+    #[serde(alias = "price_hint")]
     pub price_hint: i32,
+    #[serde(alias = "current_trading_period")]
     pub current_trading_period: TradingPeriod,
-    #[serde(default)]
+    #[serde(default, alias = "trading_periods")]
     pub trading_periods: Option<Vec<Vec<PeriodInfo>>>,
+    #[serde(alias = "data_granularity")]
     pub data_granularity: String,
     pub range: String,
+    #[serde(alias = "valid_ranges")]
     pub valid_ranges: Vec<String>,
 }
 
-#[derive(Deserialize, Debug)]
+impl YMetaData {
+    /// Date and time of the first trade of the symbol
+    pub fn first_trade_datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.first_trade_date)
+    }
+
+    /// Date and time of the regular market price
+    pub fn regular_market_datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.regular_market_time)
+    }
+
+    /// Regular market price as decimal, rounded to the number of decimal places given by `price_hint`
+    pub fn decimal_regular_market_price(&self) -> Option<Decimal> {
+        decimal_from_f64(self.regular_market_price, price_hint_decimals(self.price_hint))
+    }
+
+    /// IANA time zone of the exchange, e.g. `Europe/Berlin`
+    pub fn exchange_timezone(&self) -> Option<&'static Tz> {
+        timezones::get_by_name(&self.exchange_timezone_name)
+    }
+
+    /// Convert a timestamp to the local time of the exchange, taking daylight saving time
+    /// into account. If the time zone of the exchange is unknown, the offset of the
+    /// trading period containing the timestamp is used, or the current offset otherwise.
+    pub fn to_exchange_time(&self, timestamp: i64) -> OffsetDateTime {
+        let time = datetime_from_timestamp(timestamp);
+        match self.exchange_timezone() {
+            Some(tz) => time.to_timezone(tz),
+            None => {
+                let gmtoffset = self
+                    .trading_periods
+                    .iter()
+                    .flatten()
+                    .flatten()
+                    .find(|period| period.start <= timestamp && timestamp < period.end)
+                    .map_or(self.gmtoffset, |period| period.gmtoffset);
+                time.to_offset(UtcOffset::from_whole_seconds(gmtoffset).unwrap_or(UtcOffset::UTC))
+            }
+        }
+    }
+
+    /// Date of a timestamp in the local time of the exchange, e.g. the trading date of a daily bar
+    pub fn trading_date(&self, timestamp: i64) -> Date {
+        self.to_exchange_time(timestamp).date()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 pub struct TradingPeriod {
     pub pre: PeriodInfo,
     pub regular: PeriodInfo,
     pub post: PeriodInfo,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct PeriodInfo {
     pub timezone: String,
-    pub start: u32,
-    pub end: u32,
+    pub start: i64,
+    pub end: i64,
     pub gmtoffset: i32,
 }
 
-#[derive(Deserialize, Debug)]
+impl PeriodInfo {
+    /// Start of the trading period
+    pub fn start_datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.start)
+    }
+
+    /// End of the trading period
+    pub fn end_datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.end)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 pub struct QuoteBlock {
-    quote: Vec<QuoteList>,
+    pub(crate) quote: Vec<QuoteList>,
     #[serde(default)]
-    adjclose: Option<Vec<AdjClose>>,
+    pub(crate) adjclose: Option<Vec<AdjClose>>,
 }
 
 impl QuoteBlock {
-    fn get_ith_quote(&self, timestamp: u64, i: usize) -> Result<Quote, YahooError> {
+    fn get_ith_quote_opt(&self, timestamp: i64, i: usize) -> QuoteOpt {
+        let adjclose = match &self.adjclose {
+            Some(adjclose) => adjclose[0].adjclose[i],
+            None => None,
+        };
+        let quote = &self.quote[0];
+        QuoteOpt {
+            timestamp,
+            open: quote.open[i],
+            high: quote.high[i],
+            low: quote.low[i],
+            volume: quote.volume[i],
+            close: quote.close[i],
+            adjclose,
+        }
+    }
+
+    fn get_ith_quote(&self, timestamp: i64, i: usize) -> Result<Quote, YahooError> {
         let adjclose = match &self.adjclose {
             Some(adjclose) => adjclose[0].adjclose[i],
             None => None,
@@ -183,7 +563,7 @@ impl QuoteBlock {
         let quote = &self.quote[0];
         // reject if close is not set
         if quote.close[i].is_none() {
-            return Err(YahooError::EmptyDataSet);
+            return Err(YahooError::empty_data_set());
         }
         Ok(Quote {
             timestamp,
@@ -197,12 +577,12 @@ impl QuoteBlock {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct AdjClose {
-    adjclose: Vec<Option<f64>>,
+    pub(crate) adjclose: Vec<Option<f64>>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct QuoteList {
     pub volume: Vec<Option<u64>>,
     pub high: Vec<Option<f64>>,
@@ -211,17 +591,19 @@ pub struct QuoteList {
     pub open: Vec<Option<f64>>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct EventsBlock {
-    pub splits: Option<HashMap<u64, Split>>,
-    pub dividends: Option<HashMap<u64, Dividend>>,
+    pub splits: Option<HashMap<i64, Split>>,
+    pub dividends: Option<HashMap<i64, Dividend>>,
+    #[serde(rename(deserialize = "capitalGains"), alias = "capital_gains")]
+    pub capital_gains: Option<HashMap<i64, CapitalGain>>,
 }
 
 /// This structure simply models a split that has occured.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Split {
     /// This is the date (timestamp) when the split occured
-    pub date: u64,
+    pub date: i64,
     /// Numerator of the split. For instance a 1:5 split means you get 5 share
     /// wherever you had one before the split. (Here the numerator is 1 and 
     /// denom is 5). A reverse split is considered as nothing but a regular 
@@ -233,15 +615,356 @@ pub struct Split {
     /// split with a numerator > denom.
     pub denominator: u64,
     /// A textual representation of the split.
-    #[serde(rename = "splitRatio")]
+    #[serde(rename(deserialize = "splitRatio"), alias = "split_ratio")]
     pub split_ratio: String,
 }
 
 /// This structure simply models a dividend which has been recorded.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Dividend {
     /// This is the price of the dividend
     pub amount: f64,
     /// This is the ex-dividend date
-    pub date: u64,
+    pub date: i64,
+}
+
+/// This structure models a distribution of capital gains, e.g. by a mutual fund.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CapitalGain {
+    /// Amount distributed per share
+    pub amount: f64,
+    /// This is the ex-date of the distribution
+    pub date: i64,
+}
+
+impl Split {
+    /// Date and time of the split
+    pub fn datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.date)
+    }
+}
+
+impl Dividend {
+    /// Ex-dividend date and time
+    pub fn datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.date)
+    }
+
+    /// Amount of the dividend as decimal, rounded to the given number of decimal places
+    pub fn decimal_amount(&self, decimals: u32) -> Option<Decimal> {
+        decimal_from_f64(self.amount, decimals)
+    }
+}
+
+impl CapitalGain {
+    /// Ex-date and time of the distribution
+    pub fn datetime(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.date)
+    }
+
+    /// Amount of the distribution as decimal, rounded to the given number of decimal places
+    pub fn decimal_amount(&self, decimals: u32) -> Option<Decimal> {
+        decimal_from_f64(self.amount, decimals)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use time::macros::{date, datetime};
+
+    use super::*;
+    use crate::utils::tests::assert_serde_round_trip;
+
+    pub(crate) fn chart_json() -> serde_json::Value {
+        serde_json::json!({
+            "chart": {
+                "result": [{
+                    "meta": {
+                        "currency": "USD",
+                        "symbol": "IBM",
+                        "exchangeName": "NYQ",
+                        "instrumentType": "EQUITY",
+                        "firstTradeDate": -2461593600i64,
+                        "regularMarketTime": 1593806402,
+                        "gmtoffset": -14400,
+                        "timezone": "EDT",
+                        "exchangeTimezoneName": "America/New_York",
+                        "regularMarketPrice": 122.03,
+                        "chartPreviousClose": 120.0,
+                        "priceHint": 2,
+                        "currentTradingPeriod": {
+                            "pre": { "timezone": "EDT", "start": 1593763200, "end": 1593783000, "gmtoffset": -14400 },
+                            "regular": { "timezone": "EDT", "start": 1593783000, "end": 1593806400, "gmtoffset": -14400 },
+                            "post": { "timezone": "EDT", "start": 1593806400, "end": 1593820800, "gmtoffset": -14400 }
+                        },
+                        "dataGranularity": "1d",
+                        "range": "",
+                        "validRanges": ["1d", "5d", "1mo"]
+                    },
+                    "timestamp": [1593610200, 1593696600, 1593783000],
+                    "events": {
+                        "dividends": {
+                            "1593696600": { "amount": 1.63, "date": 1593696600 }
+                        },
+                        "splits": {
+                            "1593610200": { "date": 1593610200, "numerator": 2, "denominator": 1, "splitRatio": "2:1" }
+                        }
+                    },
+                    "indicators": {
+                        "quote": [{
+                            "volume": [3998500, null, 3475600],
+                            "high": [121.5, 122.9, 123.0],
+                            "close": [119.7, 121.1, 122.03],
+                            "low": [118.3, null, 121.0],
+                            "open": [120.3, 121.2, 121.5]
+                        }],
+                        "adjclose": [{ "adjclose": [110.7, 113.5, 114.4] }]
+                    }
+                }],
+                "error": null
+            }
+        })
+    }
+
+    /// Same chart as [`chart_json`] without adjusted close prices, like intraday quotes
+    pub(crate) fn chart_json_without_adjclose() -> serde_json::Value {
+        let mut json = chart_json();
+        json["chart"]["result"][0]["indicators"]
+            .as_object_mut()
+            .unwrap()
+            .remove("adjclose");
+        json
+    }
+
+    /// Daily AAPL quotes around the dividend of 2020-08-07 and the 4:1 split of 2020-08-31
+    /// (not every trading day), with prices, volumes and dividend amounts adjusted for the
+    /// split like in yahoo!'s responses. The adjusted close also accounts for the dividends
+    /// after 2020-09-01 with a constant factor of 0.97.
+    pub(crate) fn split_chart_json() -> serde_json::Value {
+        let mut json = chart_json();
+        let result = &mut json["chart"]["result"][0];
+        result["meta"]["symbol"] = serde_json::json!("AAPL");
+        result["meta"]["exchangeName"] = serde_json::json!("NMS");
+        result["timestamp"] = serde_json::json!([1596720600, 1596807000, 1598621400, 1598880600, 1598967000]);
+        result["events"] = serde_json::json!({
+            "dividends": {
+                "1596807000": { "amount": 0.205, "date": 1596807000 }
+            },
+            "splits": {
+                "1598880600": { "date": 1598880600, "numerator": 4, "denominator": 1, "splitRatio": "4:1" }
+            }
+        });
+        result["indicators"] = serde_json::json!({
+            "quote": [{
+                "volume": [202428800, 198045600, 187630000, 225702700, 151948100],
+                "high": [114.4125, 113.675, 126.4425, 131.0, 134.8],
+                "close": [113.9025, 111.1125, 124.8075, 129.04, 134.18],
+                "low": [109.7975, 110.2925, 124.5775, 126.0, 130.53],
+                "open": [110.405, 113.205, 126.0125, 127.58, 132.76]
+            }],
+            "adjclose": [{ "adjclose": [110.2866, 107.7791, 121.0633, 125.1688, 130.1546] }]
+        });
+        json
+    }
+
+    #[test]
+    fn test_typed_timestamps() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let meta = &response.chart.result[0].meta;
+        assert_eq!(meta.first_trade_datetime(), datetime!(1891-12-30 08:00 UTC));
+        assert_eq!(meta.regular_market_datetime(), datetime!(2020-07-03 20:00:02 UTC));
+        let quotes = response.quotes().unwrap();
+        assert_eq!(quotes[0].datetime(), datetime!(2020-07-01 13:30 UTC));
+        assert_eq!(response.splits().unwrap()[0].datetime(), datetime!(2020-07-01 13:30 UTC));
+        assert_eq!(response.dividends().unwrap()[0].datetime(), datetime!(2020-07-02 13:30 UTC));
+    }
+
+    #[test]
+    fn test_decimal_quotes() {
+        let mut json = chart_json();
+        json["chart"]["result"][0]["indicators"]["quote"][0]["close"][0] =
+            serde_json::json!(119.69999694824219);
+        json["chart"]["result"][0]["meta"]["regularMarketPrice"] = serde_json::json!(0.1 + 0.2);
+        let response = YResponse::from_json(json).unwrap();
+        let quotes = response.decimal_quotes().unwrap();
+        assert_eq!(quotes.len(), 3);
+        assert_eq!(quotes[0].close, Decimal::new(11970, 2));
+        assert_eq!(quotes[2].close, Decimal::new(12203, 2));
+        assert_eq!(response.last_decimal_quote().unwrap(), quotes[2]);
+        // missing values are `None` rather than zero
+        assert_eq!(quotes[1].low, None);
+        assert_eq!(quotes[1].volume, None);
+        assert_eq!(quotes[0].adjclose, Some(Decimal::new(1107, 1)));
+        let meta = &response.chart.result[0].meta;
+        assert_eq!(meta.decimal_regular_market_price(), Some(Decimal::new(30, 2)));
+        let dividend = &response.dividends().unwrap()[0];
+        assert_eq!(dividend.decimal_amount(4), Some(Decimal::new(163, 2)));
+
+        // prices which can't be represented as decimal are an error rather than zero
+        let mut json = chart_json();
+        json["chart"]["result"][0]["indicators"]["quote"][0]["open"][0] = serde_json::json!(1e300);
+        let response = YResponse::from_json(json).unwrap();
+        assert!(matches!(response.decimal_quotes(), Err(YahooError::DataInconsistency { .. })));
+    }
+
+    #[test]
+    fn test_quotes_opt() {
+        let mut json = chart_json();
+        json["chart"]["result"][0]["indicators"]["quote"][0]["close"][2] = serde_json::Value::Null;
+        let response = YResponse::from_json(json).unwrap();
+
+        let quotes = response.quotes_opt(MissingValuePolicy::Keep).unwrap();
+        assert_eq!(quotes.len(), 3);
+        assert_eq!(quotes[1].volume, None);
+        assert_eq!(quotes[1].low, None);
+        assert_eq!(quotes[2].close, None);
+
+        let quotes = response.quotes_opt(MissingValuePolicy::Drop).unwrap();
+        assert_eq!(quotes.len(), 1);
+        assert!(quotes[0].is_complete());
+
+        let quotes = response.quotes_opt(MissingValuePolicy::ForwardFill).unwrap();
+        assert_eq!(quotes.len(), 3);
+        assert_eq!(quotes[1].low, Some(118.3));
+        assert_eq!(quotes[1].volume, None);
+        assert_eq!(quotes[2].close, Some(121.1));
+
+        // intraday quotes come without adjusted close
+        let mut json = chart_json_without_adjclose();
+        let result = &mut json["chart"]["result"][0];
+        result["meta"]["dataGranularity"] = serde_json::json!("1m");
+        result["indicators"]["quote"][0]["low"][1] = serde_json::json!(118.3);
+        result["indicators"]["quote"][0]["volume"][1] = serde_json::json!(3998500);
+        let response = YResponse::from_json(json).unwrap();
+        let quotes = response.quotes_opt(MissingValuePolicy::Drop).unwrap();
+        assert_eq!(quotes.len(), 3);
+        assert!(quotes.iter().all(|quote| quote.is_complete() && quote.adjclose.is_none()));
+        json = chart_json();
+        json["chart"]["result"][0]["indicators"]["adjclose"][0]["adjclose"][0] = serde_json::Value::Null;
+        let response = YResponse::from_json(json).unwrap();
+        assert_eq!(response.quotes_opt(MissingValuePolicy::Drop).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let json = serde_json::to_value(&response).unwrap();
+        // field names are in snake case rather than yahoo!'s camel case
+        let result = &json["chart"]["result"][0];
+        assert_eq!(result["meta"]["exchange_name"], "NYQ");
+        assert!(result["meta"].get("exchangeName").is_none());
+        assert_eq!(result["events"]["splits"]["1593610200"]["split_ratio"], "2:1");
+        let cached = YResponse::from_json(json).unwrap();
+        assert_eq!(cached.quotes().unwrap(), response.quotes().unwrap());
+        assert_eq!(cached.dividends().unwrap().len(), 1);
+        assert_eq!(cached.splits().unwrap()[0].split_ratio, "2:1");
+        assert_serde_round_trip(&response.chart.result[0]);
+
+        let quote = &response.quotes().unwrap()[0];
+        let json = serde_json::to_value(quote).unwrap();
+        assert_eq!(json["timestamp"], 1593610200);
+        assert_eq!(serde_json::from_value::<Quote>(json).unwrap(), *quote);
+
+        let quotes = response.decimal_quotes().unwrap();
+        let json = serde_json::to_string(&quotes).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<DecimalQuote>>(&json).unwrap(), quotes);
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let body = chart_json().to_string();
+        let response = YResponse::from_slice(body.as_bytes()).unwrap();
+        let expected = YResponse::from_json(chart_json()).unwrap();
+        assert_eq!(response.quotes().unwrap(), expected.quotes().unwrap());
+        assert_eq!(response.dividends().unwrap().len(), 1);
+
+        let body = r#"{"chart":{"result":null,"error":{"code":"Not Found","description":"No data found, symbol may be delisted"}}}"#;
+        let error = YResponse::from_slice(body.as_bytes()).unwrap_err();
+        assert!(matches!(&error, YahooError::SymbolNotFound { symbol, .. } if symbol.is_empty()));
+        // the symbol is known once the error is related to the request
+        let error = error.with_context(RequestContext::new("https://query1.finance.yahoo.com", Some("XXXX")));
+        assert!(matches!(error, YahooError::SymbolNotFound { symbol, .. } if symbol == "XXXX"));
+        assert!(matches!(
+            YResponse::from_slice(b"{\"chart\": "),
+            Err(YahooError::InvalidJson { .. })
+        ));
+        assert!(matches!(
+            YResponse::from_slice(b"{\"chart\": {}}"),
+            Err(YahooError::DeserializeFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_multiple_results() {
+        let mut json = chart_json();
+        let mut second = json["chart"]["result"][0].clone();
+        second["meta"]["symbol"] = "MSFT".into();
+        second["indicators"]["quote"][0]["close"] = serde_json::json!([200.1, null]);
+        json["chart"]["result"][0]["comparisons"] = serde_json::json!([{
+            "symbol": "^GSPC",
+            "chartPreviousClose": 3100.29,
+            "open": [3105.9, 3112.2, 3140.0],
+            "close": [3115.8, 3130.0, 3155.2]
+        }]);
+        json["chart"]["result"].as_array_mut().unwrap().push(second);
+        let response = YResponse::from_json(json).unwrap();
+
+        assert_eq!(response.results().count(), 2);
+        assert_eq!(response.first_result().unwrap().meta.symbol, "IBM");
+        assert_eq!(response.quotes().unwrap().len(), 3);
+        // the inconsistent second block does not affect the first one
+        let msft = response.result("MSFT").unwrap();
+        assert!(matches!(msft.quotes(), Err(YahooError::DataInconsistency { .. })));
+        assert!(response.result("AAPL").is_none());
+
+        let block = response.first_result().unwrap();
+        let gspc = block.comparison("^GSPC").unwrap();
+        assert_eq!(gspc.close.len(), block.timestamp.len());
+        assert_eq!(gspc.chart_previous_close, Some(3100.29));
+        assert!(gspc.high.is_empty());
+    }
+
+    #[test]
+    fn test_empty_result() {
+        let json = serde_json::json!({ "chart": { "result": [], "error": null } });
+        let response = YResponse::from_json(json).unwrap();
+        assert!(matches!(response.first_result(), Err(YahooError::EmptyDataSet { .. })));
+        assert!(matches!(response.quotes(), Err(YahooError::EmptyDataSet { .. })));
+        assert!(matches!(response.last_quote(), Err(YahooError::EmptyDataSet { .. })));
+        assert!(matches!(response.splits(), Err(YahooError::EmptyDataSet { .. })));
+        assert!(matches!(response.dividends(), Err(YahooError::EmptyDataSet { .. })));
+        assert!(matches!(response.decimal_quotes(), Err(YahooError::EmptyDataSet { .. })));
+        assert_eq!(response.results().count(), 0);
+    }
+
+    #[test]
+    fn test_exchange_time() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let mut meta = response.chart.result.into_iter().next().unwrap().meta;
+        // daylight saving time in New York
+        assert_eq!(
+            meta.to_exchange_time(1593610200),
+            datetime!(2020-07-01 09:30 -4)
+        );
+        assert_eq!(
+            meta.to_exchange_time(datetime!(2020-01-02 14:30 UTC).unix_timestamp()),
+            datetime!(2020-01-02 09:30 -5)
+        );
+        // daily bars of the ASX start at midnight local time, i.e. the day before in UTC
+        meta.exchange_timezone_name = "Australia/Sydney".to_string();
+        let quote = Quote {
+            timestamp: datetime!(2020-07-05 14:00 UTC).unix_timestamp(),
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            volume: 0,
+            close: 1.0,
+            adjclose: 1.0,
+        };
+        assert_eq!(quote.datetime().date(), date!(2020 - 07 - 05));
+        assert_eq!(quote.trading_date(&meta), date!(2020 - 07 - 06));
+        // unknown time zones fall back to the offset provided by yahoo!
+        meta.exchange_timezone_name = "Unknown".to_string();
+        assert_eq!(quote.exchange_datetime(&meta), datetime!(2020-07-05 10:00 -4));
+    }
 }
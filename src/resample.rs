@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use time::{Date, Duration, Month, PrimitiveDateTime, Time};
+
+use super::YahooError;
+use crate::quotes::{PeriodInfo, QuoteOpt, YMetaData, YQuoteBlock, YResponse};
+
+/// Interval of resampled quotes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResampleInterval {
+    /// Intraday interval of the given number of minutes, e.g. 10
+    Minutes(u32),
+    /// Intraday interval of the given number of hours, e.g. 4
+    Hours(u32),
+    /// Calendar week starting on Monday
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+/// Edge of the interval the timestamp of a resampled quote refers to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BarLabel {
+    /// Start of the interval, like the quotes of yahoo!
+    Left,
+    /// End of the interval
+    Right,
+}
+
+/// Unix timestamp of midnight at the given date in the local time of the exchange
+fn local_midnight(meta: &YMetaData, date: Date) -> i64 {
+    let midnight = PrimitiveDateTime::new(date, Time::MIDNIGHT);
+    let offset = meta
+        .to_exchange_time(midnight.assume_utc().unix_timestamp())
+        .offset();
+    midnight.assume_offset(offset).unix_timestamp()
+}
+
+/// First day of the calendar period containing the date, and of the following period
+fn calendar_period(date: Date, interval: ResampleInterval) -> (Date, Date) {
+    let first_of = |year: i32, month: u8| {
+        Date::from_calendar_date(year, Month::January.nth_next(month - 1), 1).unwrap()
+    };
+    let month = date.month() as u8;
+    let (start_month, months) = match interval {
+        ResampleInterval::Week => {
+            let start = date - Duration::days(date.weekday().number_days_from_monday() as i64);
+            return (start, start + Duration::days(7));
+        }
+        ResampleInterval::Month => (month, 1),
+        ResampleInterval::Quarter => ((month - 1) / 3 * 3 + 1, 3),
+        _ => (1, 12),
+    };
+    let end_month = start_month + months;
+    let end = if end_month > 12 {
+        first_of(date.year() + 1, end_month - 12)
+    } else {
+        first_of(date.year(), end_month)
+    };
+    (first_of(date.year(), start_month), end)
+}
+
+/// Edges of the interval containing the timestamp. Intraday intervals are aligned to the
+/// start of the trading period containing the timestamp and end with it at the latest;
+/// outside of the trading periods they are aligned to midnight in the local time of the
+/// exchange and clipped to the gap between the surrounding trading periods. Calendar
+/// intervals follow the trading dates.
+fn bucket(meta: &YMetaData, sessions: &[&PeriodInfo], interval: ResampleInterval, timestamp: i64) -> (i64, i64) {
+    let width = match interval {
+        ResampleInterval::Minutes(minutes) => 60 * minutes as i64,
+        ResampleInterval::Hours(hours) => 3600 * hours as i64,
+        _ => {
+            let (start, end) = calendar_period(meta.trading_date(timestamp), interval);
+            return (local_midnight(meta, start), local_midnight(meta, end));
+        }
+    };
+    let i = sessions.partition_point(|session| session.start <= timestamp);
+    match sessions[..i].last().filter(|session| timestamp < session.end) {
+        Some(session) => {
+            let left = session.start + (timestamp - session.start) / width * width;
+            (left, (left + width).min(session.end))
+        }
+        None => {
+            let midnight = local_midnight(meta, meta.trading_date(timestamp));
+            let left = midnight + (timestamp - midnight).div_euclid(width) * width;
+            let previous_end = sessions[..i].iter().map(|session| session.end).max();
+            let next_start = sessions.get(i).map(|session| session.start);
+            (
+                previous_end.map_or(left, |end| left.max(end)),
+                next_start.map_or(left + width, |start| (left + width).min(start)),
+            )
+        }
+    }
+}
+
+/// Aggregate quotes of an interval: first open, highest high, lowest low, last close and
+/// adjusted close and total volume, ignoring missing values
+fn aggregate(timestamp: i64, quotes: &[QuoteOpt]) -> QuoteOpt {
+    let max = |a: Option<f64>, b: Option<f64>| match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        _ => a.or(b),
+    };
+    let min = |a: Option<f64>, b: Option<f64>| match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        _ => a.or(b),
+    };
+    QuoteOpt {
+        timestamp,
+        open: quotes.iter().find_map(|quote| quote.open),
+        high: quotes.iter().map(|quote| quote.high).fold(None, max),
+        low: quotes.iter().map(|quote| quote.low).fold(None, min),
+        volume: quotes
+            .iter()
+            .filter_map(|quote| quote.volume)
+            .fold(None, |sum, volume| Some(sum.unwrap_or(0) + volume)),
+        close: quotes.iter().rev().find_map(|quote| quote.close),
+        adjclose: quotes.iter().rev().find_map(|quote| quote.adjclose),
+    }
+}
+
+impl YResponse {
+    /// Quotes resampled to a coarser interval, see [`YQuoteBlock::resample`]
+    pub fn resample(&self, interval: ResampleInterval, label: BarLabel) -> Result<Vec<QuoteOpt>, YahooError> {
+        self.first_result()?.resample(interval, label)
+    }
+}
+
+impl YQuoteBlock {
+    /// Resample the quotes to a coarser interval, e.g. 1 minute quotes to 10 minutes or
+    /// daily quotes to weeks. Intraday intervals don't span trading periods given by
+    /// `trading_periods` of the meta data, i.e. start with each session and end with it
+    /// at the latest; intervals outside of the sessions end with the start of the next
+    /// session at the latest. Calendar intervals are based on the dates in the time zone of the
+    /// exchange. Intervals without quotes are omitted.
+    ///
+    /// Returns `InvalidArgument` if the number of minutes or hours is zero.
+    pub fn resample(&self, interval: ResampleInterval, label: BarLabel) -> Result<Vec<QuoteOpt>, YahooError> {
+        if matches!(interval, ResampleInterval::Minutes(0) | ResampleInterval::Hours(0)) {
+            return Err(YahooError::InvalidArgument {
+                message: "resample interval must be positive".to_string(),
+            });
+        }
+        let quotes: Vec<QuoteOpt> = self.quote_series()?.iter().collect();
+        let mut sessions: Vec<&PeriodInfo> = self.meta.trading_periods.iter().flatten().flatten().collect();
+        sessions.sort_by_key(|session| session.start);
+
+        let mut resampled = Vec::new();
+        let mut start = 0;
+        while start < quotes.len() {
+            let (left, right) = bucket(&self.meta, &sessions, interval, quotes[start].timestamp);
+            let len = quotes[start..]
+                .iter()
+                .take_while(|quote| bucket(&self.meta, &sessions, interval, quote.timestamp).0 == left)
+                .count();
+            let timestamp = match label {
+                BarLabel::Left => left,
+                BarLabel::Right => right,
+            };
+            resampled.push(aggregate(timestamp, &quotes[start..start + len]));
+            start += len;
+        }
+        Ok(resampled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::quotes::tests::chart_json;
+
+    #[test]
+    fn test_resample_calendar() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+
+        let weeks = response.resample(ResampleInterval::Week, BarLabel::Left).unwrap();
+        assert_eq!(weeks.len(), 1);
+        assert_eq!(weeks[0].timestamp, datetime!(2020-06-29 00:00 -4).unix_timestamp());
+        assert_eq!(weeks[0].open, Some(120.3));
+        assert_eq!(weeks[0].high, Some(123.0));
+        assert_eq!(weeks[0].low, Some(118.3));
+        assert_eq!(weeks[0].close, Some(122.03));
+        assert_eq!(weeks[0].adjclose, Some(114.4));
+        assert_eq!(weeks[0].volume, Some(3998500 + 3475600));
+
+        let quarters = response.resample(ResampleInterval::Quarter, BarLabel::Right).unwrap();
+        assert_eq!(quarters[0].timestamp, datetime!(2020-10-01 00:00 -4).unix_timestamp());
+        let years = response.resample(ResampleInterval::Year, BarLabel::Right).unwrap();
+        assert_eq!(years[0].timestamp, datetime!(2021-01-01 00:00 -5).unix_timestamp());
+    }
+
+    #[test]
+    fn test_resample_intraday() {
+        let session_start = datetime!(2020-07-01 09:30 -4).unix_timestamp();
+        let session_end = datetime!(2020-07-01 16:00 -4).unix_timestamp();
+        // a pre-market quote, twelve 1 minute quotes at the open, one before the close and
+        // a post-market quote
+        let mut timestamps = vec![session_start - 1800];
+        timestamps.extend((0..12).map(|i| session_start + 60 * i));
+        timestamps.push(session_end - 60);
+        timestamps.push(session_end + 1800);
+        let prices: Vec<f64> = (0..timestamps.len()).map(|i| 100.0 + i as f64).collect();
+
+        let mut json = chart_json();
+        let result = &mut json["chart"]["result"][0];
+        result["meta"]["dataGranularity"] = serde_json::json!("1m");
+        result["meta"]["tradingPeriods"] = serde_json::json!([[
+            { "timezone": "EDT", "start": session_start, "end": session_end, "gmtoffset": -14400 }
+        ]]);
+        result["timestamp"] = serde_json::json!(timestamps);
+        result["indicators"] = serde_json::json!({
+            "quote": [{
+                "volume": vec![10; timestamps.len()],
+                "high": prices,
+                "close": prices,
+                "low": prices,
+                "open": prices
+            }]
+        });
+        result.as_object_mut().unwrap().remove("events");
+        let response = YResponse::from_json(json).unwrap();
+
+        let bars = response.resample(ResampleInterval::Minutes(10), BarLabel::Left).unwrap();
+        let labels: Vec<i64> = bars.iter().map(|bar| bar.timestamp).collect();
+        assert_eq!(
+            labels,
+            vec![
+                session_start - 1800,
+                session_start,
+                session_start + 600,
+                session_end - 600,
+                session_end + 1800
+            ]
+        );
+        assert_eq!(bars[1].open, Some(101.0));
+        assert_eq!(bars[1].close, Some(110.0));
+        assert_eq!(bars[1].volume, Some(100));
+        assert_eq!(bars[2].high, Some(112.0));
+        assert_eq!(bars[2].adjclose, None);
+
+        // the pre-market interval ends with the start of the session and the last interval
+        // of the session with its end
+        let bars = response.resample(ResampleInterval::Hours(4), BarLabel::Right).unwrap();
+        let labels: Vec<i64> = bars.iter().map(|bar| bar.timestamp).collect();
+        assert_eq!(
+            labels,
+            vec![
+                session_start,
+                session_start + 4 * 3600,
+                session_end,
+                datetime!(2020-07-01 20:00 -4).unix_timestamp()
+            ]
+        );
+        let bars = response.resample(ResampleInterval::Hours(4), BarLabel::Left).unwrap();
+        assert_eq!(bars[0].timestamp, datetime!(2020-07-01 08:00 -4).unix_timestamp());
+        assert_eq!(bars[0].close, Some(100.0));
+        // the post-market interval starts with the end of the session
+        let bars = response.resample(ResampleInterval::Hours(3), BarLabel::Left).unwrap();
+        assert_eq!(bars.last().unwrap().timestamp, session_end);
+
+        assert!(matches!(
+            response.resample(ResampleInterval::Minutes(0), BarLabel::Left),
+            Err(YahooError::InvalidArgument { .. })
+        ));
+    }
+}
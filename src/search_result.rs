@@ -1,73 +1,88 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 
 use super::YahooError;
+use crate::utils::datetime_from_timestamp;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct YSearchResultOpt {
     pub count: u32,
     pub quotes: Vec<YQuoteItemOpt>,
     pub news: Vec<YNewsItem>,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
 pub struct YQuoteItemOpt {
     pub exchange: String,
-    #[serde(rename = "shortname")]
+    #[serde(rename(deserialize = "shortname"), alias = "short_name")]
     pub short_name: Option<String>,
+    #[serde(alias = "quote_type")]
     pub quote_type: String,
     pub symbol: String,
     pub index: String,
     pub score: f64,
-    #[serde(rename = "typeDisp")]
+    #[serde(rename(deserialize = "typeDisp"), alias = "type_display")]
     pub type_display: String,
-    #[serde(rename = "longname")]
+    #[serde(rename(deserialize = "longname"), alias = "long_name")]
     pub long_name: Option<String>,
+    #[serde(alias = "is_yahoo_finance")]
     pub is_yahoo_finance: bool,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct YNewsItem {
     pub uuid: String,
     pub title: String,
     pub publisher: String,
     pub link: String,
-    #[serde(rename = "providerPublishTime")]
-    pub provider_publish_time: u64,
-    #[serde(rename = "type")]
+    #[serde(rename(deserialize = "providerPublishTime"), alias = "provider_publish_time")]
+    pub provider_publish_time: i64,
+    #[serde(rename(deserialize = "type"), alias = "newstype")]
     pub newstype: String,
 }
 
+impl YNewsItem {
+    /// Date and time the news has been published by the provider
+    pub fn publish_time(&self) -> OffsetDateTime {
+        datetime_from_timestamp(self.provider_publish_time)
+    }
+}
+
 impl YSearchResultOpt {
     pub fn from_json(json: serde_json::Value) -> Result<YSearchResultOpt, YahooError> {
-        match serde_json::from_value(json) {
-            Ok(v) => Ok(v),
-            Err(e) => Err(YahooError::DeserializeFailed(e.to_string())),
-        }
+        serde_json::from_value(json).map_err(YahooError::from)
+    }
+
+    /// Deserialize the search result directly from the bytes of the body
+    pub fn from_slice(body: &[u8]) -> Result<YSearchResultOpt, YahooError> {
+        serde_json::from_slice(body).map_err(YahooError::from)
     }
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct YSearchResult {
     pub count: u32,
     pub quotes: Vec<YQuoteItem>,
     pub news: Vec<YNewsItem>,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
 pub struct YQuoteItem {
     pub exchange: String,
-    #[serde(rename = "shortname")]
+    #[serde(rename(deserialize = "shortname"), alias = "short_name")]
     pub short_name: String,
+    #[serde(alias = "quote_type")]
     pub quote_type: String,
     pub symbol: String,
     pub index: String,
     pub score: f64,
-    #[serde(rename = "typeDisp")]
+    #[serde(rename(deserialize = "typeDisp"), alias = "type_display")]
     pub type_display: String,
-    #[serde(rename = "longname")]
+    #[serde(rename(deserialize = "longname"), alias = "long_name")]
     pub long_name: String,
+    #[serde(alias = "is_yahoo_finance")]
     pub is_yahoo_finance: bool,
 }
 
@@ -112,3 +127,39 @@ impl YSearchResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::tests::assert_serde_round_trip;
+
+    #[test]
+    fn test_search_result_serde_round_trip() {
+        let json = serde_json::json!({
+            "count": 1,
+            "quotes": [{
+                "exchange": "NMS",
+                "shortname": "Apple Inc.",
+                "quoteType": "EQUITY",
+                "symbol": "AAPL",
+                "index": "quotes",
+                "score": 2014100.0,
+                "typeDisp": "Equity",
+                "longname": "Apple Inc.",
+                "isYahooFinance": true
+            }],
+            "news": [{
+                "uuid": "2e1c3b5a",
+                "title": "Apple unveils new products",
+                "publisher": "Reuters",
+                "link": "https://finance.yahoo.com/news/apple",
+                "providerPublishTime": 1666915200,
+                "type": "STORY"
+            }]
+        });
+        let result: YSearchResult = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(result.quotes[0].short_name, "Apple Inc.");
+        assert_serde_round_trip(&result);
+        assert_serde_round_trip(&YSearchResultOpt::from_json(json).unwrap());
+    }
+}
@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::iso8601_date;
+
+/// Type of a filing submitted to the SEC
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum SecFilingType {
+    /// Annual report (10-K)
+    AnnualReport,
+    /// Quarterly report (10-Q)
+    QuarterlyReport,
+    /// Current report on material events (8-K)
+    CurrentReport,
+    /// Annual report of a foreign private issuer (20-F)
+    ForeignAnnualReport,
+    /// Current report of a foreign private issuer (6-K)
+    ForeignCurrentReport,
+    /// Definitive proxy statement (DEF 14A)
+    ProxyStatement,
+    /// Registration statement (S-1)
+    Registration,
+    /// Any other form, e.g. `SC 13G`
+    Other(String),
+}
+
+impl From<String> for SecFilingType {
+    fn from(form: String) -> Self {
+        match form.as_str() {
+            "10-K" => SecFilingType::AnnualReport,
+            "10-Q" => SecFilingType::QuarterlyReport,
+            "8-K" => SecFilingType::CurrentReport,
+            "20-F" => SecFilingType::ForeignAnnualReport,
+            "6-K" => SecFilingType::ForeignCurrentReport,
+            "DEF 14A" => SecFilingType::ProxyStatement,
+            "S-1" => SecFilingType::Registration,
+            _ => SecFilingType::Other(form),
+        }
+    }
+}
+
+impl From<SecFilingType> for String {
+    fn from(filing_type: SecFilingType) -> Self {
+        filing_type.form().to_string()
+    }
+}
+
+impl SecFilingType {
+    /// Name of the form as used by the SEC, e.g. `10-K`
+    pub fn form(&self) -> &str {
+        match self {
+            SecFilingType::AnnualReport => "10-K",
+            SecFilingType::QuarterlyReport => "10-Q",
+            SecFilingType::CurrentReport => "8-K",
+            SecFilingType::ForeignAnnualReport => "20-F",
+            SecFilingType::ForeignCurrentReport => "6-K",
+            SecFilingType::ProxyStatement => "DEF 14A",
+            SecFilingType::Registration => "S-1",
+            SecFilingType::Other(form) => form,
+        }
+    }
+}
+
+/// This structure models a filing submitted to the SEC
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
+pub struct SecFiling {
+    /// The date the filing has been submitted
+    #[serde(with = "iso8601_date")]
+    pub date: time::Date,
+    #[serde(rename(deserialize = "type"), alias = "filing_type")]
+    pub filing_type: SecFilingType,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Link to the filing in the EDGAR database
+    #[serde(default, alias = "edgar_url")]
+    pub edgar_url: Option<String>,
+    #[serde(default)]
+    pub exhibits: Vec<SecFilingExhibit>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SecFilingExhibit {
+    #[serde(rename(deserialize = "type"), alias = "exhibit_type")]
+    pub exhibit_type: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
+pub(crate) struct YSecFilingsData {
+    pub sec_filings: Option<YSecFilings>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub(crate) struct YSecFilings {
+    pub filings: Vec<SecFiling>,
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+    use crate::quote_summary::YQuoteSummaryResponse;
+    use crate::utils::tests::assert_serde_round_trip;
+
+    #[test]
+    fn test_deserialize_sec_filings() {
+        let json = serde_json::json!({
+            "quoteSummary": {
+                "result": [{
+                    "secFilings": {
+                        "filings": [{
+                            "date": "2022-10-28",
+                            "epochDate": 1666915200,
+                            "type": "10-K",
+                            "title": "Annual Report",
+                            "edgarUrl": "https://yahoo.brand.edgar-online.com/DisplayFiling.aspx?dcn=0000320193-22-000108",
+                            "exhibits": [{ "url": "https://example.com/ex21.htm", "type": "EX-21.1" }],
+                            "maxAge": 1
+                        }, {
+                            "date": "2022-08-05",
+                            "epochDate": 1659657600,
+                            "type": "SC 13G/A",
+                            "title": "Amended Statement of Ownership",
+                            "edgarUrl": "https://yahoo.brand.edgar-online.com/DisplayFiling.aspx?dcn=0000834237-22-004345",
+                            "maxAge": 1
+                        }, {
+                            "date": "2022-07-29",
+                            "epochDate": 1659052800,
+                            "type": "10-Q",
+                            "maxAge": 1
+                        }],
+                        "maxAge": 86400
+                    }
+                }],
+                "error": null
+            }
+        });
+        let filings = YQuoteSummaryResponse::<YSecFilingsData>::from_slice(json.to_string().as_bytes())
+            .unwrap()
+            .into_result()
+            .unwrap()
+            .sec_filings
+            .unwrap()
+            .filings;
+        assert_eq!(filings.len(), 3);
+        assert_serde_round_trip(&filings);
+        assert_eq!(filings[0].date, date!(2022 - 10 - 28));
+        assert_eq!(filings[0].filing_type, SecFilingType::AnnualReport);
+        assert_eq!(filings[0].exhibits[0].exhibit_type, "EX-21.1");
+        assert_eq!(filings[1].filing_type.form(), "SC 13G/A");
+        assert!(filings[1].exhibits.is_empty());
+        assert_eq!(filings[1].title.as_deref(), Some("Amended Statement of Ownership"));
+        assert_eq!(filings[2].title, None);
+        assert_eq!(filings[2].edgar_url, None);
+    }
+}
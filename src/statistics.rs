@@ -1,59 +1,228 @@
-use serde::Deserialize;
-use time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
 
 use super::YahooError;
 use crate::utils::{IntegerValue, DecimalValue, OffsetDateTimeValue};
 
-time::serde::format_description!(iso8601_date, Date, "[year repr:full]-[month repr:numerical]-[day padding:zero]");
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "PascalCase"))]
 pub struct YStatisticsResponse {
+    #[serde(alias = "quote_summary_store")]
     pub quote_summary_store: Option<YQuoteSummaryStore>
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
 pub struct YQuoteSummaryStore {
+    #[serde(default, alias = "default_key_statistics")]
     pub default_key_statistics: KeyStatistics,
+    #[serde(default, alias = "financial_data")]
     pub financial_data: Option<FinancialData>,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+/// Key statistics of the `defaultKeyStatistics` module. All fields are optional,
+/// since yahoo! only returns the ones applicable to the quote type.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
 pub struct KeyStatistics {
+    #[serde(alias = "price_hint")]
+    pub price_hint: Option<IntegerValue>,
+    #[serde(alias = "enterprise_value")]
     pub enterprise_value: Option<IntegerValue>,
+    #[serde(rename(deserialize = "forwardPE"), alias = "forward_pe")]
+    pub forward_pe: Option<DecimalValue>,
+    #[serde(alias = "profit_margins")]
+    pub profit_margins: Option<DecimalValue>,
+    #[serde(alias = "float_shares")]
     pub float_shares: Option<IntegerValue>,
+    #[serde(alias = "shares_outstanding")]
+    pub shares_outstanding: Option<IntegerValue>,
+    #[serde(alias = "implied_shares_outstanding")]
+    pub implied_shares_outstanding: Option<IntegerValue>,
+    #[serde(alias = "shares_short")]
+    pub shares_short: Option<IntegerValue>,
+    #[serde(alias = "shares_short_prior_month")]
+    pub shares_short_prior_month: Option<IntegerValue>,
+    #[serde(alias = "shares_short_previous_month_date")]
+    pub shares_short_previous_month_date: Option<OffsetDateTimeValue>,
+    #[serde(alias = "date_short_interest")]
+    pub date_short_interest: Option<OffsetDateTimeValue>,
+    #[serde(alias = "shares_percent_shares_out")]
+    pub shares_percent_shares_out: Option<DecimalValue>,
+    #[serde(alias = "held_percent_insiders")]
     pub held_percent_insiders: Option<DecimalValue>,
+    #[serde(alias = "held_percent_institutions")]
     pub held_percent_institutions: Option<DecimalValue>,
-    pub most_recent_quarter: Option<OffsetDateTimeValue>,
-    pub net_income_to_common: Option<IntegerValue>,
-    pub next_fiscal_year_end: Option<OffsetDateTimeValue>,
+    #[serde(alias = "short_ratio")]
+    pub short_ratio: Option<DecimalValue>,
+    #[serde(alias = "short_percent_of_float")]
+    pub short_percent_of_float: Option<DecimalValue>,
+    pub beta: Option<DecimalValue>,
+    #[serde(rename(deserialize = "beta3Year"), alias = "beta_3_year")]
+    pub beta_3_year: Option<DecimalValue>,
+    #[serde(alias = "morning_star_overall_rating")]
+    pub morning_star_overall_rating: Option<IntegerValue>,
+    #[serde(alias = "morning_star_risk_rating")]
+    pub morning_star_risk_rating: Option<IntegerValue>,
+    pub category: Option<String>,
+    #[serde(alias = "fund_family")]
+    pub fund_family: Option<String>,
+    #[serde(alias = "legal_type")]
+    pub legal_type: Option<String>,
+    #[serde(alias = "fund_inception_date")]
+    pub fund_inception_date: Option<OffsetDateTimeValue>,
+    #[serde(alias = "book_value")]
+    pub book_value: Option<DecimalValue>,
+    #[serde(alias = "price_to_book")]
     pub price_to_book: Option<DecimalValue>,
-    pub shares_outstanding: Option<IntegerValue>,
+    #[serde(alias = "annual_report_expense_ratio")]
+    pub annual_report_expense_ratio: Option<DecimalValue>,
+    #[serde(alias = "annual_holdings_turnover")]
+    pub annual_holdings_turnover: Option<DecimalValue>,
+    #[serde(alias = "ytd_return")]
+    pub ytd_return: Option<DecimalValue>,
+    #[serde(alias = "three_year_average_return")]
+    pub three_year_average_return: Option<DecimalValue>,
+    #[serde(alias = "five_year_average_return")]
+    pub five_year_average_return: Option<DecimalValue>,
+    #[serde(alias = "total_assets")]
     pub total_assets: Option<IntegerValue>,
+    #[serde(rename(deserialize = "yield"), alias = "yield_")]
+    pub yield_: Option<DecimalValue>,
+    #[serde(alias = "price_to_sales_trailing12_months")]
+    pub price_to_sales_trailing12_months: Option<DecimalValue>,
+    #[serde(alias = "last_fiscal_year_end")]
+    pub last_fiscal_year_end: Option<OffsetDateTimeValue>,
+    #[serde(alias = "next_fiscal_year_end")]
+    pub next_fiscal_year_end: Option<OffsetDateTimeValue>,
+    #[serde(alias = "most_recent_quarter")]
+    pub most_recent_quarter: Option<OffsetDateTimeValue>,
+    #[serde(alias = "earnings_quarterly_growth")]
+    pub earnings_quarterly_growth: Option<DecimalValue>,
+    #[serde(alias = "revenue_quarterly_growth")]
+    pub revenue_quarterly_growth: Option<DecimalValue>,
+    #[serde(alias = "net_income_to_common")]
+    pub net_income_to_common: Option<IntegerValue>,
+    #[serde(alias = "trailing_eps")]
     pub trailing_eps: Option<DecimalValue>,
+    #[serde(alias = "forward_eps")]
+    pub forward_eps: Option<DecimalValue>,
+    #[serde(alias = "peg_ratio")]
+    pub peg_ratio: Option<DecimalValue>,
+    /// Textual representation of the last split, e.g. `4:1`
+    #[serde(alias = "last_split_factor")]
+    pub last_split_factor: Option<String>,
+    #[serde(alias = "last_split_date")]
+    pub last_split_date: Option<OffsetDateTimeValue>,
+    #[serde(alias = "enterprise_to_revenue")]
+    pub enterprise_to_revenue: Option<DecimalValue>,
+    #[serde(alias = "enterprise_to_ebitda")]
+    pub enterprise_to_ebitda: Option<DecimalValue>,
+    #[serde(rename(deserialize = "52WeekChange"), alias = "week_52_change")]
+    pub week_52_change: Option<DecimalValue>,
+    #[serde(rename(deserialize = "SandP52WeekChange"), alias = "s_and_p_52_week_change")]
+    pub s_and_p_52_week_change: Option<DecimalValue>,
+    #[serde(alias = "last_dividend_value")]
+    pub last_dividend_value: Option<DecimalValue>,
+    #[serde(alias = "last_dividend_date")]
+    pub last_dividend_date: Option<OffsetDateTimeValue>,
+    #[serde(alias = "last_cap_gain")]
+    pub last_cap_gain: Option<DecimalValue>,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+/// Financial data of the `financialData` module. All fields are optional,
+/// since yahoo! only returns the ones applicable to the quote type.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"), default)]
 pub struct FinancialData {
+    #[serde(alias = "current_price")]
+    pub current_price: Option<DecimalValue>,
+    #[serde(alias = "target_high_price")]
+    pub target_high_price: Option<DecimalValue>,
+    #[serde(alias = "target_low_price")]
+    pub target_low_price: Option<DecimalValue>,
+    #[serde(alias = "target_mean_price")]
+    pub target_mean_price: Option<DecimalValue>,
+    #[serde(alias = "target_median_price")]
+    pub target_median_price: Option<DecimalValue>,
+    /// Mean analyst recommendation from 1 (strong buy) to 5 (sell)
+    #[serde(alias = "recommendation_mean")]
+    pub recommendation_mean: Option<DecimalValue>,
+    /// Textual analyst recommendation, e.g. `buy`
+    #[serde(alias = "recommendation_key")]
+    pub recommendation_key: Option<String>,
+    #[serde(alias = "number_of_analyst_opinions")]
+    pub number_of_analyst_opinions: Option<IntegerValue>,
+    #[serde(alias = "total_cash")]
+    pub total_cash: Option<IntegerValue>,
+    #[serde(alias = "total_cash_per_share")]
+    pub total_cash_per_share: Option<DecimalValue>,
+    pub ebitda: Option<IntegerValue>,
+    #[serde(alias = "total_debt")]
+    pub total_debt: Option<IntegerValue>,
+    #[serde(alias = "quick_ratio")]
+    pub quick_ratio: Option<DecimalValue>,
+    #[serde(alias = "current_ratio")]
     pub current_ratio: Option<DecimalValue>,
-    pub debt_to_equity: DecimalValue,
-    pub ebitda: IntegerValue,
+    #[serde(alias = "total_revenue")]
+    pub total_revenue: Option<IntegerValue>,
+    #[serde(alias = "debt_to_equity")]
+    pub debt_to_equity: Option<DecimalValue>,
+    #[serde(alias = "revenue_per_share")]
+    pub revenue_per_share: Option<DecimalValue>,
+    #[serde(alias = "return_on_assets")]
+    pub return_on_assets: Option<DecimalValue>,
+    #[serde(alias = "return_on_equity")]
+    pub return_on_equity: Option<DecimalValue>,
+    #[serde(alias = "gross_profits")]
+    pub gross_profits: Option<IntegerValue>,
+    #[serde(alias = "free_cashflow")]
+    pub free_cashflow: Option<IntegerValue>,
+    #[serde(alias = "operating_cashflow")]
+    pub operating_cashflow: Option<IntegerValue>,
+    #[serde(alias = "earnings_growth")]
+    pub earnings_growth: Option<DecimalValue>,
+    #[serde(alias = "revenue_growth")]
+    pub revenue_growth: Option<DecimalValue>,
+    #[serde(alias = "gross_margins")]
+    pub gross_margins: Option<DecimalValue>,
+    #[serde(alias = "ebitda_margins")]
+    pub ebitda_margins: Option<DecimalValue>,
+    #[serde(alias = "operating_margins")]
+    pub operating_margins: Option<DecimalValue>,
+    #[serde(alias = "profit_margins")]
+    pub profit_margins: Option<DecimalValue>,
+    #[serde(alias = "financial_currency")]
     pub financial_currency: Option<String>,
-    pub free_cashflow: IntegerValue,
-    pub operating_cashflow: IntegerValue,
-    pub quick_ratio: DecimalValue,
-    pub return_on_assets: DecimalValue,
-    pub total_cash: IntegerValue,
-    pub total_debt: IntegerValue,
-    pub total_revenue: IntegerValue,
 }
 
-
 impl YStatisticsResponse {
     pub fn from_json(json: serde_json::Value) -> Result<YStatisticsResponse, YahooError> {
-        serde_json::from_value(json).map_err(|e| YahooError::DeserializeFailed(e.to_string()))
+        serde_json::from_value(json).map_err(YahooError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::tests::assert_serde_round_trip;
+
+    #[test]
+    fn test_statistics_serde_round_trip() {
+        let json = serde_json::json!({
+            "QuoteSummaryStore": {
+                "defaultKeyStatistics": {
+                    "forwardPE": { "raw": 24.5, "fmt": "24.50" },
+                    "52WeekChange": { "raw": -0.12, "fmt": "-12.00%" },
+                    "sharesOutstanding": { "raw": 15908100096u64, "fmt": "15.91B" }
+                },
+                "financialData": {
+                    "recommendationKey": "buy"
+                }
+            }
+        });
+        let response = YStatisticsResponse::from_json(json).unwrap();
+        let statistics = &response.quote_summary_store.as_ref().unwrap().default_key_statistics;
+        assert_eq!(statistics.shares_outstanding.unwrap().0, Some(15908100096));
+        assert_serde_round_trip(&response);
     }
 }
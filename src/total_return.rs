@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+use super::YahooError;
+use crate::quotes::{CapitalGain, Dividend, Quote, YQuoteBlock, YResponse};
+
+/// Options of a total return index
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TotalReturnOptions {
+    /// Value of the index at the first quote, 100 by default
+    pub base: f64,
+    /// Fraction of the dividends withheld as tax, e.g. 0.15 for 15%; 0 by default
+    pub withholding_tax: f64,
+    /// Fraction of the capital gains distributions withheld as tax; 0 by default
+    pub capital_gains_tax: f64,
+}
+
+impl Default for TotalReturnOptions {
+    fn default() -> Self {
+        TotalReturnOptions {
+            base: 100.0,
+            withholding_tax: 0.0,
+            capital_gains_tax: 0.0,
+        }
+    }
+}
+
+/// Value of a total return index at a quote
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct TotalReturn {
+    /// Unix timestamp (seconds since the epoch, UTC) of the start of the period
+    pub timestamp: i64,
+    pub value: f64,
+}
+
+/// Total return index of a position bought at the first close, given as pairs of
+/// timestamp and close in ascending order. Dividends and capital gains net of taxes are
+/// reinvested at the close of their ex-date, i.e. the first quote at or after their date.
+/// The closes and amounts are expected to be adjusted for splits, like yahoo!'s, so the
+/// number of shares only changes by reinvesting. Events at or before the first quote are
+/// ignored.
+fn total_return(
+    closes: &[(i64, f64)],
+    dividends: &[Dividend],
+    capital_gains: &[CapitalGain],
+    options: &TotalReturnOptions,
+) -> Vec<TotalReturn> {
+    let (first, first_close) = match closes.first() {
+        Some(first) => *first,
+        None => return Vec::new(),
+    };
+    // distributions per share net of taxes, in order of their ex-date
+    let mut distributions: Vec<(i64, f64)> = dividends
+        .iter()
+        .map(|dividend| (dividend.date, dividend.amount * (1.0 - options.withholding_tax)))
+        .chain(
+            capital_gains
+                .iter()
+                .map(|gain| (gain.date, gain.amount * (1.0 - options.capital_gains_tax))),
+        )
+        .filter(|(date, _)| *date > first)
+        .collect();
+    distributions.sort_by_key(|(date, _)| *date);
+    let mut distributions = distributions.into_iter().peekable();
+    let mut shares = options.base / first_close;
+    closes
+        .iter()
+        .map(|&(timestamp, close)| {
+            let mut cash = 0.0;
+            while let Some((_, amount)) = distributions.next_if(|(date, _)| *date <= timestamp) {
+                cash += shares * amount;
+            }
+            shares += cash / close;
+            TotalReturn {
+                timestamp,
+                value: shares * close,
+            }
+        })
+        .collect()
+}
+
+/// Total return index of a quote history, reinvesting the dividends and capital gains at the
+/// close of their ex-date, e.g. for quotes read by [`read_quotes_csv`](crate::read_quotes_csv).
+/// The quotes need to be in chronological order and the closes adjusted for splits, if any.
+pub fn total_return_index(
+    quotes: &[Quote],
+    dividends: &[Dividend],
+    capital_gains: &[CapitalGain],
+    options: &TotalReturnOptions,
+) -> Vec<TotalReturn> {
+    let closes: Vec<(i64, f64)> = quotes.iter().map(|quote| (quote.timestamp, quote.close)).collect();
+    total_return(&closes, dividends, capital_gains, options)
+}
+
+impl YResponse {
+    /// Total return index of the quotes, see [`YQuoteBlock::total_return`]
+    pub fn total_return(&self, options: &TotalReturnOptions) -> Result<Vec<TotalReturn>, YahooError> {
+        self.first_result()?.total_return(options)
+    }
+}
+
+impl YQuoteBlock {
+    /// Total return index of a position bought at the first close, reinvesting the dividends
+    /// and capital gains net of taxes at the close of their ex-date. Splits need no handling,
+    /// since yahoo!'s closes and distributions are already adjusted for them. Quotes without
+    /// close are skipped.
+    pub fn total_return(&self, options: &TotalReturnOptions) -> Result<Vec<TotalReturn>, YahooError> {
+        let series = self.quote_series()?;
+        let closes: Vec<(i64, f64)> = series
+            .timestamps()
+            .iter()
+            .zip(series.close())
+            .filter_map(|(timestamp, close)| close.map(|close| (*timestamp, close)))
+            .collect();
+        Ok(total_return(
+            &closes,
+            &self.dividends()?,
+            &self.capital_gains()?,
+            options,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quotes::tests::{chart_json, split_chart_json};
+
+    #[test]
+    fn test_total_return() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+
+        let index = response.total_return(&TotalReturnOptions::default()).unwrap();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index[0].value, 100.0);
+        // 1.63 per share reinvested at 121.1
+        let shares = 100.0 / 119.7 * (1.0 + 1.63 / 121.1);
+        assert!((index[1].value - shares * 121.1).abs() < 1e-9);
+        assert!((index[2].value - shares * 122.03).abs() < 1e-9);
+
+        let options = TotalReturnOptions {
+            withholding_tax: 0.25,
+            ..TotalReturnOptions::default()
+        };
+        let taxed = response.total_return(&options).unwrap();
+        let shares = 100.0 / 119.7 * (1.0 + 0.75 * 1.63 / 121.1);
+        assert!((taxed[1].value - shares * 121.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_total_return_across_split() {
+        let response = YResponse::from_json(split_chart_json()).unwrap();
+        let index = response.total_return(&TotalReturnOptions::default()).unwrap();
+        // the index follows the split-adjusted closes without a jump at the split
+        let shares = 100.0 / 113.9025 * (1.0 + 0.205 / 111.1125);
+        for (value, close) in index[1..].iter().zip([111.1125, 124.8075, 129.04, 134.18]) {
+            assert!((value.value - shares * close).abs() < 1e-9);
+        }
+        assert!((index[3].value / index[2].value - 129.04 / 124.8075).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_capital_gains() {
+        let mut json = chart_json();
+        json["chart"]["result"][0]["events"]["capitalGains"] = serde_json::json!({
+            "1593783000": { "amount": 2.4, "date": 1593783000 }
+        });
+        let response = YResponse::from_json(json).unwrap();
+        let capital_gains = response.capital_gains().unwrap();
+        assert_eq!(capital_gains.len(), 1);
+        assert_eq!(capital_gains[0].amount, 2.4);
+
+        let options = TotalReturnOptions {
+            capital_gains_tax: 0.5,
+            ..TotalReturnOptions::default()
+        };
+        let index = response.total_return(&options).unwrap();
+        let shares = 100.0 / 119.7 * (1.0 + 1.63 / 121.1);
+        let shares = shares * (1.0 + 1.2 / 122.03);
+        assert!((index[2].value - shares * 122.03).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_total_return_index() {
+        let response = YResponse::from_json(chart_json()).unwrap();
+        let quotes = response.quotes().unwrap();
+        let dividends = response.dividends().unwrap();
+        let index = total_return_index(&quotes[1..], &dividends, &[], &TotalReturnOptions::default());
+        // the dividend at the first quote is ignored
+        assert_eq!(index[0].value, 100.0);
+        assert!((index[1].value - 100.0 * 122.03 / 121.1).abs() < 1e-9);
+        assert!(total_return_index(&[], &dividends, &[], &TotalReturnOptions::default()).is_empty());
+    }
+}
@@ -1,110 +1,224 @@
-use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+// serde format of dates like `2022-10-28`
+time::serde::format_description!(
+    pub(crate) iso8601_date,
+    Date,
+    "[year repr:full]-[month repr:numerical]-[day padding:zero]"
+);
+
+/// Convert a unix timestamp to a date and time in UTC. Timestamps outside of
+/// the range supported by `time` are clamped to the nearest representable value.
+pub fn datetime_from_timestamp(timestamp: i64) -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp(timestamp).unwrap_or_else(|_| {
+        if timestamp < 0 {
+            PrimitiveDateTime::new(Date::MIN, Time::MIDNIGHT).assume_utc()
+        } else {
+            PrimitiveDateTime::new(Date::MAX, Time::MIDNIGHT).assume_utc()
+        }
+    })
+}
 
-use rust_decimal::Decimal;
-use serde::de::{Visitor, MapAccess, Deserialize, Deserializer};
-use time::{Date, OffsetDateTime};
+/// Convert a price to a decimal rounded to the given number of decimal places. The conversion
+/// is based on the shortest representation of the value, which avoids the binary rounding
+/// noise of the floating point value. `None` if the value is not finite or out of the range
+/// of `Decimal`.
+pub fn decimal_from_f64(value: f64, decimals: u32) -> Option<Decimal> {
+    Decimal::from_str(&value.to_string())
+        .ok()
+        .or_else(|| Decimal::from_f64(value))
+        .map(|value| round_price(value, decimals))
+}
 
-#[derive(Debug)]
-pub struct IntegerValue(pub Option<i64>);
+/// Round a price to the given number of decimal places, rounding midpoints away from zero
+pub fn round_price(value: Decimal, decimals: u32) -> Decimal {
+    value.round_dp_with_strategy(decimals, RoundingStrategy::MidpointAwayFromZero)
+}
 
-struct IntegerValueVisitor;
+/// Number of decimal places of prices given yahoo!'s `priceHint`
+pub fn price_hint_decimals(price_hint: i32) -> u32 {
+    price_hint.clamp(0, 28) as u32
+}
 
-impl<'de> Visitor<'de> for IntegerValueVisitor {
-    type Value = IntegerValue;
+/// Deserialize `null` as the default value, e.g. to an empty vector
+pub fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
 
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("object with raw property")
+/// Convert a raw JSON number to an integer, truncating any fractional part.
+/// Anything else (e.g. `"Infinity"`) is treated as missing.
+fn integer_from_json(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+        _ => None,
     }
+}
 
-    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
-    where
-        V: MapAccess<'de>,
-    {
-        let mut value = None;
-        while let Some(key) = map.next_key::<String>()? {
-            if let "raw" = key.as_str() {
-                value = Some(map.next_value()?);
-            }
-        }
-        Ok(IntegerValue(value))
-    }
+/// Convert a raw JSON number or numeric string to a decimal without loss of precision.
+/// Anything else (e.g. `"Infinity"`) is treated as missing.
+fn decimal_from_json(value: &Value) -> Option<Decimal> {
+    let s = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => return None,
+    };
+    Decimal::from_str(&s)
+        .or_else(|_| Decimal::from_scientific(&s))
+        .ok()
+}
+
+/// Extract the value of a field of the API, which is either wrapped in an object
+/// with `raw` and `fmt` properties (as sent by yahoo!) or a plain value (as serialized
+/// by this crate).
+fn raw_value<'de, D>(deserializer: D) -> Result<Value, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Object(mut map) => map.remove("raw").unwrap_or(Value::Null),
+        value => value,
+    })
 }
 
+/// Integer field of the API, serialized as plain number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegerValue(pub Option<i64>);
+
 impl<'de> Deserialize<'de> for IntegerValue {
     fn deserialize<D>(deserializer: D) -> Result<IntegerValue, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_map(IntegerValueVisitor)
+        Ok(IntegerValue(integer_from_json(&raw_value(deserializer)?)))
     }
 }
 
-#[derive(Debug)]
-pub struct DecimalValue(pub Option<Decimal>);
-
-struct DecimalValueVisitor;
-
-impl<'de> Visitor<'de> for DecimalValueVisitor {
-    type Value = DecimalValue;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("object with raw property")
-    }
-
-    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+impl Serialize for IntegerValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
-        V: MapAccess<'de>,
+        S: Serializer,
     {
-        let mut value = None;
-        while let Some(key) = map.next_key::<String>()? {
-            if let "raw" = key.as_str() {
-                value = Some(map.next_value()?);
-            }
-        }
-        Ok(DecimalValue(value))
+        self.0.serialize(serializer)
     }
 }
 
+/// Decimal field of the API, serialized as string to preserve its precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecimalValue(pub Option<Decimal>);
+
 impl<'de> Deserialize<'de> for DecimalValue {
     fn deserialize<D>(deserializer: D) -> Result<DecimalValue, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_map(DecimalValueVisitor)
+        Ok(DecimalValue(decimal_from_json(&raw_value(deserializer)?)))
     }
 }
 
-#[derive(Debug)]
-pub struct OffsetDateTimeValue(pub Option<OffsetDateTime>);
-
-struct DateValueVisitor;
-
-impl<'de> Visitor<'de> for DateValueVisitor {
-    type Value = OffsetDateTimeValue;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("object with raw property")
-    }
-
-    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+impl Serialize for DecimalValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
-        V: MapAccess<'de>,
+        S: Serializer,
     {
-        let mut value = None;
-        while let Some(key) = map.next_key::<String>()? {
-            if let "raw" = key.as_str() {
-                value = OffsetDateTime::from_unix_timestamp(map.next_value()?).ok();
-            }
-        }
-        Ok(OffsetDateTimeValue(value))
+        self.0.serialize(serializer)
     }
 }
 
+/// Date and time field of the API, serialized as unix timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetDateTimeValue(pub Option<OffsetDateTime>);
+
 impl<'de> Deserialize<'de> for OffsetDateTimeValue {
     fn deserialize<D>(deserializer: D) -> Result<OffsetDateTimeValue, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_map(DateValueVisitor)
+        let value = integer_from_json(&raw_value(deserializer)?)
+            .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok());
+        Ok(OffsetDateTimeValue(value))
+    }
+}
+
+impl Serialize for OffsetDateTimeValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.map(|time| time.unix_timestamp()).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde::de::DeserializeOwned;
+    use serde_json::json;
+
+    use super::*;
+
+    /// Pointer to the first null in the given JSON value, if any
+    fn first_null(value: &Value, pointer: &str) -> Option<String> {
+        let escape = |key: &str| key.replace('~', "~0").replace('/', "~1");
+        match value {
+            Value::Null => Some(pointer.to_string()),
+            Value::Array(values) => values
+                .iter()
+                .enumerate()
+                .find_map(|(i, value)| first_null(value, &format!("{}/{}", pointer, i))),
+            Value::Object(values) => values
+                .iter()
+                .find_map(|(key, value)| first_null(value, &format!("{}/{}", pointer, escape(key)))),
+            _ => None,
+        }
+    }
+
+    /// Check that all fields of the value survive a round trip through the stable
+    /// serialization format. Missing values (nulls) of the serialized value are filled
+    /// first with sample values of the serialized format, so every optional field is
+    /// covered. Panics naming the first field which is not read back, e.g. for lack of
+    /// an alias of its serialized name.
+    pub(crate) fn assert_serde_round_trip<T: Serialize + DeserializeOwned>(value: &T) {
+        let samples = [json!(1), json!(1.5), json!(true), json!("2021-01-01"), json!({}), json!([])];
+        let round_trip = |json: Value| -> Option<Value> {
+            serde_json::from_value::<T>(json)
+                .ok()
+                .map(|value| serde_json::to_value(value).unwrap())
+        };
+        let mut json = serde_json::to_value(value).unwrap();
+        while let Some(pointer) = first_null(&json, "") {
+            json = samples
+                .iter()
+                .find_map(|sample| {
+                    let mut filled = json.clone();
+                    *filled.pointer_mut(&pointer).unwrap() = sample.clone();
+                    round_trip(filled).filter(|json| json.pointer(&pointer).is_some_and(|v| !v.is_null()))
+                })
+                .unwrap_or_else(|| panic!("field {} is not read back", pointer));
+        }
+        assert_eq!(round_trip(json.clone()), Some(json));
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all(serialize = "snake_case", deserialize = "camelCase"))]
+    struct Aliased {
+        #[serde(alias = "short_name")]
+        short_name: Option<String>,
+        long_name: Option<String>,
+    }
+
+    #[test]
+    #[should_panic(expected = "field /long_name is not read back")]
+    fn test_serde_round_trip_missing_alias() {
+        let value: Aliased = serde_json::from_value(json!({ "shortName": "IBM" })).unwrap();
+        assert_serde_round_trip(&value);
     }
 }
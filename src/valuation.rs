@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use time::Date;
+
+use crate::adjustment::split_ratio;
+use crate::async_impl::FinancialReport;
+use crate::financials::SharesOutstanding;
+use crate::quotes::{Quote, Split};
+
+/// Market valuation of a company at the time of a quote
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Valuation {
+    pub timestamp: i64,
+    pub close: f64,
+    /// Number of shares outstanding as of the last report before the quote, adjusted for
+    /// the splits after the report like the close
+    pub shares: f64,
+    pub market_cap: f64,
+    /// Market capitalization plus total debt minus cash and cash equivalents,
+    /// if both have been reported before the quote
+    pub enterprise_value: Option<f64>,
+}
+
+/// Product of the ratios of the splits after the given date (UTC), i.e. the number of
+/// shares now per share at the end of the date
+fn split_factor_after(splits: &[Split], date: Date) -> f64 {
+    let end = date.next_day().unwrap_or(date).midnight().assume_utc().unix_timestamp();
+    splits
+        .iter()
+        .filter(|split| split.date >= end)
+        .filter_map(split_ratio)
+        .product()
+}
+
+/// Combine a quote history with the financial reports of the company to a
+/// valuation history. Quotes prior to the first reported share count are skipped.
+///
+/// Reports are keyed by the end of their period (`asOfDate`), not by the date they were
+/// published, and each quote is matched with the most recent values for periods ending at
+/// or before its date. Figures are therefore used before they were actually known, which
+/// introduces look-ahead bias e.g. in backtests; shift the report dates by the publication
+/// lag beforehand if this matters.
+///
+/// The closing prices of yahoo! are adjusted for all splits up to now, so the reported share
+/// counts are scaled by the ratios of the given splits after the end of the report period.
+/// Pass all splits since the first report, including those after the last quote.
+pub fn valuation_history(
+    quotes: &[Quote],
+    reports: &HashMap<Date, FinancialReport>,
+    splits: &[Split],
+) -> Vec<Valuation> {
+    let mut reports = reports.iter().collect::<Vec<_>>();
+    reports.sort_unstable_by_key(|(date, _)| **date);
+
+    let mut next_report = 0;
+    let mut shares = None;
+    let mut debt = None;
+    let mut cash = None;
+    let mut valuations = Vec::new();
+    for quote in quotes {
+        let date = quote.datetime().date();
+        while next_report < reports.len() && *reports[next_report].0 <= date {
+            let report = reports[next_report].1;
+            let date = *reports[next_report].0;
+            shares = SharesOutstanding::from_report(date, report)
+                .shares()
+                .map(|shares| shares * split_factor_after(splits, date))
+                .or(shares);
+            // current debt alone would understate the enterprise value
+            debt = report.total_debt.or(debt);
+            cash = report.cash_and_cash_equivalents.or(cash);
+            next_report += 1;
+        }
+        if let Some(shares) = shares {
+            let market_cap = quote.close * shares;
+            valuations.push(Valuation {
+                timestamp: quote.timestamp,
+                close: quote.close,
+                shares,
+                market_cap,
+                enterprise_value: debt.zip(cash).map(|(debt, cash)| market_cap + debt - cash),
+            });
+        }
+    }
+    valuations
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{date, datetime};
+    use time::OffsetDateTime;
+
+    use super::*;
+
+    fn quote(time: OffsetDateTime, close: f64) -> Quote {
+        Quote {
+            timestamp: time.unix_timestamp(),
+            open: close,
+            high: close,
+            low: close,
+            volume: 0,
+            close,
+            adjclose: close,
+        }
+    }
+
+    #[test]
+    fn test_valuation_history() {
+        let quotes = vec![
+            quote(datetime!(2020-12-30 14:30 UTC), 9.0),
+            quote(datetime!(2021-01-04 14:30 UTC), 10.0),
+            quote(datetime!(2021-06-30 14:30 UTC), 11.0),
+            quote(datetime!(2021-07-01 14:30 UTC), 12.0),
+        ];
+        let mut reports = HashMap::new();
+        reports.insert(
+            date!(2020 - 12 - 31),
+            FinancialReport {
+                ordinary_shares_number: Some(100.0),
+                current_debt: Some(20.0),
+                cash_and_cash_equivalents: Some(50.0),
+                ..Default::default()
+            },
+        );
+        reports.insert(
+            date!(2021 - 06 - 30),
+            FinancialReport {
+                basic_average_shares: Some(200.0),
+                total_debt: Some(300.0),
+                ..Default::default()
+            },
+        );
+        let valuations = valuation_history(&quotes, &reports, &[]);
+        assert_eq!(valuations.len(), 3);
+        assert_eq!(valuations[0].market_cap, 1000.0);
+        assert_eq!(valuations[0].enterprise_value, None);
+        assert_eq!(valuations[1].shares, 200.0);
+        assert_eq!(valuations[1].market_cap, 2200.0);
+        assert_eq!(valuations[1].enterprise_value, Some(2450.0));
+        assert_eq!(valuations[2].market_cap, 2400.0);
+    }
+
+    #[test]
+    fn test_valuation_history_with_split() {
+        // the closes after the 4:1 split are adjusted for it, like all closes of yahoo!
+        let quotes = vec![
+            quote(datetime!(2020-08-03 14:30 UTC), 110.0),
+            quote(datetime!(2020-09-01 14:30 UTC), 134.0),
+        ];
+        let mut reports = HashMap::new();
+        reports.insert(
+            date!(2020 - 06 - 30),
+            FinancialReport {
+                ordinary_shares_number: Some(4_300.0),
+                ..Default::default()
+            },
+        );
+        let splits = vec![Split {
+            date: datetime!(2020-08-31 13:30 UTC).unix_timestamp(),
+            numerator: 4,
+            denominator: 1,
+            split_ratio: "4:1".to_string(),
+        }];
+        let valuations = valuation_history(&quotes, &reports, &splits);
+        assert_eq!(valuations[0].shares, 17_200.0);
+        assert_eq!(valuations[0].market_cap, 110.0 * 17_200.0);
+        assert_eq!(valuations[1].market_cap, 134.0 * 17_200.0);
+
+        // splits before the end of the report period are contained in its share count
+        reports.insert(
+            date!(2020 - 09 - 30),
+            FinancialReport {
+                ordinary_shares_number: Some(17_000.0),
+                ..Default::default()
+            },
+        );
+        let quotes = vec![quote(datetime!(2020-10-01 14:30 UTC), 116.0)];
+        let valuations = valuation_history(&quotes, &reports, &splits);
+        assert_eq!(valuations[0].shares, 17_000.0);
+    }
+}
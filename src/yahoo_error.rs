@@ -1,39 +1,474 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
 use std::fmt;
+use std::time::Duration;
 
-#[derive(Debug, Deserialize)]
+/// Maximum number of characters of the response body kept in the [`RequestContext`]
+const SNIPPET_LENGTH: usize = 512;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum YahooError {
-    FetchFailed(String),
-    DeserializeFailed(String),
-    ConnectionFailed,
-    InvalidJson,
-    EmptyDataSet,
-    DataInconsistency,
+    /// The request failed for a reason not covered by the other variants
+    FetchFailed {
+        message: String,
+        context: Box<RequestContext>,
+    },
+    /// The response could not be deserialized into the expected structure
+    DeserializeFailed {
+        source: serde_json::Error,
+        context: Box<RequestContext>,
+    },
+    /// The connection to the yahoo! finance server failed
+    ConnectionFailed {
+        source: BoxError,
+        context: Box<RequestContext>,
+    },
+    /// The response is not valid JSON
+    InvalidJson {
+        source: serde_json::Error,
+        context: Box<RequestContext>,
+    },
+    /// The response contains no data
+    EmptyDataSet { context: Box<RequestContext> },
+    /// The response contains data which is inconsistent, e.g. series of different lengths
+    DataInconsistency { context: Box<RequestContext> },
+    /// The requested symbol is unknown to yahoo! finance or has been delisted. The
+    /// symbol is empty if the response has been parsed without request context.
+    SymbolNotFound {
+        symbol: String,
+        context: Box<RequestContext>,
+    },
+    /// Too many requests have been sent, retry after the given duration if provided
+    RateLimited {
+        retry_after: Option<Duration>,
+        context: Box<RequestContext>,
+    },
+    /// The request has been rejected for missing or invalid credentials (e.g. crumb or cookie)
+    Unauthorized { context: Box<RequestContext> },
+    /// The requested range or interval is not supported for the symbol
+    InvalidRange {
+        message: String,
+        context: Box<RequestContext>,
+    },
+    /// The yahoo! finance server failed to process the request, the beginning of the
+    /// response body is kept in the context
+    ServerError {
+        status: u16,
+        context: Box<RequestContext>,
+    },
+    /// An argument passed to a method is invalid, e.g. a resample interval of zero minutes
+    InvalidArgument { message: String },
+    /// Reading or writing CSV data failed
+    CsvFailed { source: BoxError },
+    /// Converting data to Arrow or writing Parquet files failed
+    ArrowFailed { source: BoxError },
+    /// Converting data to a polars data frame failed
+    DataFrameFailed { source: BoxError },
+}
+
+/// Details of the request to yahoo! finance an error occurred in
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RequestContext {
+    /// URL of the request
+    pub url: Option<String>,
+    /// Symbol the data has been requested for
+    pub symbol: Option<String>,
+    /// HTTP status code of the response
+    pub status: Option<u16>,
+    /// Beginning of the response body
+    pub snippet: Option<String>,
+}
+
+impl RequestContext {
+    pub(crate) fn new(url: &str, symbol: Option<&str>) -> RequestContext {
+        RequestContext {
+            url: Some(url.to_string()),
+            symbol: symbol.map(|s| s.to_string()),
+            status: None,
+            snippet: None,
+        }
+    }
+
+    pub(crate) fn with_status(mut self, status: u16) -> RequestContext {
+        self.status = Some(status);
+        self
+    }
+
+    /// Keep the beginning of the response body
+    pub(crate) fn with_body(mut self, body: &[u8]) -> RequestContext {
+        self.snippet = Some(snippet(body));
+        self
+    }
+}
+
+/// The first `SNIPPET_LENGTH` characters of a response body
+fn snippet(body: &[u8]) -> String {
+    // a character takes at most four bytes in UTF-8
+    let prefix = &body[..body.len().min(4 * SNIPPET_LENGTH)];
+    String::from_utf8_lossy(prefix).chars().take(SNIPPET_LENGTH).collect()
 }
 
-impl std::error::Error for YahooError {
-    fn cause(&self) -> Option<&dyn std::error::Error> {
-        None
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(symbol) = &self.symbol {
+            parts.push(format!("symbol {}", symbol));
+        }
+        if let Some(url) = &self.url {
+            parts.push(format!("url {}", url));
+        }
+        if let Some(status) = self.status {
+            parts.push(format!("status {}", status));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+impl YahooError {
+    /// Details of the request the error occurred in, if it is related to a request
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Self::FetchFailed { context, .. }
+            | Self::DeserializeFailed { context, .. }
+            | Self::ConnectionFailed { context, .. }
+            | Self::InvalidJson { context, .. }
+            | Self::SymbolNotFound { context, .. }
+            | Self::RateLimited { context, .. }
+            | Self::Unauthorized { context }
+            | Self::InvalidRange { context, .. }
+            | Self::ServerError { context, .. }
+            | Self::EmptyDataSet { context }
+            | Self::DataInconsistency { context } => Some(context),
+            Self::InvalidArgument { .. }
+            | Self::CsvFailed { .. }
+            | Self::ArrowFailed { .. }
+            | Self::DataFrameFailed { .. } => None,
+        }
+    }
+
+    /// Attach the given request context, unless the error already refers to a request
+    pub(crate) fn with_context(mut self, new_context: RequestContext) -> YahooError {
+        if let Self::SymbolNotFound { symbol, .. } = &mut self {
+            if symbol.is_empty() {
+                *symbol = new_context.symbol.clone().unwrap_or_default();
+            }
+        }
+        match &mut self {
+            Self::FetchFailed { context, .. }
+            | Self::DeserializeFailed { context, .. }
+            | Self::ConnectionFailed { context, .. }
+            | Self::InvalidJson { context, .. }
+            | Self::SymbolNotFound { context, .. }
+            | Self::RateLimited { context, .. }
+            | Self::Unauthorized { context }
+            | Self::InvalidRange { context, .. }
+            | Self::ServerError { context, .. }
+            | Self::EmptyDataSet { context }
+            | Self::DataInconsistency { context } => {
+                if context.url.is_none() {
+                    **context = new_context;
+                }
+            }
+            Self::InvalidArgument { .. }
+            | Self::CsvFailed { .. }
+            | Self::ArrowFailed { .. }
+            | Self::DataFrameFailed { .. } => {}
+        }
+        self
+    }
+
+    /// Empty data set without request context, which is attached by `with_context` once known
+    pub(crate) fn empty_data_set() -> YahooError {
+        YahooError::EmptyDataSet {
+            context: Box::default(),
+        }
+    }
+
+    /// Inconsistent data without request context, which is attached by `with_context` once known
+    pub(crate) fn data_inconsistency() -> YahooError {
+        YahooError::DataInconsistency {
+            context: Box::default(),
+        }
+    }
+
+    pub(crate) fn fetch_failed(message: impl Into<String>, context: RequestContext) -> YahooError {
+        YahooError::FetchFailed {
+            message: message.into(),
+            context: Box::new(context),
+        }
+    }
+
+    /// Map an unsuccessful response of the yahoo! finance API to the matching error,
+    /// using the `{code, description}` error object in the body if present
+    pub(crate) fn from_response(
+        context: RequestContext,
+        retry_after: Option<Duration>,
+        body: &str,
+    ) -> YahooError {
+        let status = context.status.unwrap_or_default();
+        let context = context.with_body(body.as_bytes());
+        match status {
+            401 | 403 => YahooError::Unauthorized {
+                context: Box::new(context),
+            },
+            429 => YahooError::RateLimited {
+                retry_after,
+                context: Box::new(context),
+            },
+            500..=599 => YahooError::ServerError {
+                status,
+                context: Box::new(context),
+            },
+            _ => match error_detail(body) {
+                Some((code, description)) => {
+                    YahooError::from_api_error(&code, &description, context)
+                }
+                None => match (status, context.symbol.clone()) {
+                    (404, Some(symbol)) => YahooError::SymbolNotFound {
+                        symbol,
+                        context: Box::new(context),
+                    },
+                    _ => YahooError::fetch_failed(format!("Status Code: {}", status), context),
+                },
+            },
+        }
+    }
+
+    /// Map an error object (`{code, description}`) returned by the yahoo! finance API
+    /// to the matching error
+    pub(crate) fn from_api_error(
+        code: &str,
+        description: &str,
+        context: RequestContext,
+    ) -> YahooError {
+        match code {
+            "Not Found" if description.starts_with("No fundamentals data found") => {
+                YahooError::EmptyDataSet {
+                    context: Box::new(context),
+                }
+            }
+            "Not Found" => {
+                let symbol = context.symbol.clone().or_else(|| {
+                    description
+                        .rsplit_once("ticker symbol:")
+                        .map(|(_, s)| s.trim().to_string())
+                });
+                // the symbol is filled in by `with_context` once known, so that the
+                // same body gives the same error with and without request context
+                YahooError::SymbolNotFound {
+                    symbol: symbol.unwrap_or_default(),
+                    context: Box::new(context),
+                }
+            }
+            "Unauthorized" | "Forbidden" => YahooError::Unauthorized {
+                context: Box::new(context),
+            },
+            "Bad Request" | "Unprocessable Entity" => YahooError::InvalidRange {
+                message: description.to_string(),
+                context: Box::new(context),
+            },
+            _ => YahooError::fetch_failed(format!("{}: {}", code, description), context),
+        }
+    }
+}
+
+impl From<serde_json::Error> for YahooError {
+    /// Errors in the JSON syntax become `InvalidJson`, all others `DeserializeFailed`
+    fn from(source: serde_json::Error) -> YahooError {
+        if source.is_syntax() || source.is_eof() {
+            YahooError::InvalidJson {
+                source,
+                context: Box::default(),
+            }
+        } else {
+            YahooError::DeserializeFailed {
+                source,
+                context: Box::default(),
+            }
+        }
+    }
+}
+
+/// Error object as returned in the body of responses from the yahoo! finance API,
+/// e.g. `{"chart": {"result": null, "error": {"code": ..., "description": ...}}}`
+#[derive(Deserialize)]
+struct ErrorDetail {
+    code: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// Extract code and description of the error object of any endpoint from a response body
+fn error_detail(body: &str) -> Option<(String, String)> {
+    let json = serde_json::from_str::<serde_json::Value>(body).ok()?;
+    json.as_object()?
+        .values()
+        .filter_map(|endpoint| endpoint.get("error"))
+        .chain(json.get("error"))
+        .find_map(|error| serde_json::from_value::<ErrorDetail>(error.clone()).ok())
+        .map(|error| (error.code, error.description))
+}
+
+impl Error for YahooError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::DeserializeFailed { source, .. } | Self::InvalidJson { source, .. } => {
+                Some(source)
+            }
+            Self::ConnectionFailed { source, .. }
+            | Self::CsvFailed { source }
+            | Self::ArrowFailed { source }
+            | Self::DataFrameFailed { source } => {
+                Some(source.as_ref())
+            }
+            _ => None,
+        }
     }
 }
 
 impl fmt::Display for YahooError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::FetchFailed(status) => write!(
+            Self::FetchFailed { message, .. } => write!(
                 f,
-                "fetching the data from yahoo! finance failed: with status code {}",
-                status
+                "fetching the data from yahoo! finance failed: {}",
+                message
             ),
-            Self::DeserializeFailed(s) => write!(
+            Self::DeserializeFailed { source, .. } => write!(
                 f,
                 "deserializing response from yahoo! finance failed: {}",
-                &s
+                source
+            ),
+            Self::ConnectionFailed { .. } => write!(f, "connection to yahoo! finance server failed"),
+            Self::InvalidJson { .. } => write!(f, "yahoo! finance return invalid JSON format"),
+            Self::EmptyDataSet { .. } => write!(f, "yahoo! finance returned an empty data set"),
+            Self::DataInconsistency { .. } => write!(f, "yahoo! finance returned inconsistent data"),
+            Self::SymbolNotFound { symbol, .. } if symbol.is_empty() => {
+                write!(f, "symbol not found on yahoo! finance")
+            }
+            Self::SymbolNotFound { symbol, .. } => {
+                write!(f, "symbol {} not found on yahoo! finance", symbol)
+            }
+            Self::RateLimited {
+                retry_after: Some(retry_after),
+                ..
+            } => write!(
+                f,
+                "rate limited by yahoo! finance, retry after {} seconds",
+                retry_after.as_secs()
             ),
-            Self::ConnectionFailed => write!(f, "connection to yahoo! finance server failed"),
-            Self::InvalidJson => write!(f, "yahoo! finance return invalid JSON format"),
-            Self::EmptyDataSet => write!(f, "yahoo! finance returned an empty data set"),
-            Self::DataInconsistency => write!(f, "yahoo! finance returned inconsistent data"),
+            Self::RateLimited {
+                retry_after: None, ..
+            } => write!(f, "rate limited by yahoo! finance"),
+            Self::Unauthorized { .. } => write!(f, "request to yahoo! finance was not authorized"),
+            Self::InvalidRange { message, .. } => {
+                write!(f, "invalid range requested from yahoo! finance: {}", message)
+            }
+            Self::ServerError { status, .. } => {
+                write!(f, "yahoo! finance server failed with status code {}", status)
+            }
+            Self::InvalidArgument { message } => write!(f, "invalid argument: {}", message),
+            Self::CsvFailed { source } => write!(f, "reading or writing CSV failed: {}", source),
+            Self::ArrowFailed { source } => {
+                write!(f, "converting to Arrow or writing Parquet failed: {}", source)
+            }
+            Self::DataFrameFailed { source } => {
+                write!(f, "converting to a data frame failed: {}", source)
+            }
+        }?;
+        match self.context() {
+            Some(context) if context.url.is_some() => write!(f, " ({})", context),
+            _ => Ok(()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_context(status: u16, symbol: Option<&str>) -> RequestContext {
+        RequestContext::new("https://query1.finance.yahoo.com/v8/finance/chart/XXXX", symbol)
+            .with_status(status)
+    }
+
+    #[test]
+    fn test_error_from_response() {
+        let body = r#"{"chart":{"result":null,"error":{"code":"Not Found","description":"No data found, symbol may be delisted"}}}"#;
+        let error = YahooError::from_response(request_context(404, Some("XXXX")), None, body);
+        assert!(matches!(
+            &error,
+            YahooError::SymbolNotFound { symbol, .. } if symbol == "XXXX"
+        ));
+        let context = error.context().unwrap();
+        assert_eq!(context.status, Some(404));
+        assert_eq!(context.snippet.as_deref(), Some(body));
+        assert_eq!(
+            error.to_string(),
+            "symbol XXXX not found on yahoo! finance (symbol XXXX, url https://query1.finance.yahoo.com/v8/finance/chart/XXXX, status 404)"
+        );
+        let body = r#"{"quoteSummary":{"result":null,"error":{"code":"Not Found","description":"Quote not found for ticker symbol: XXXX"}}}"#;
+        assert!(matches!(
+            YahooError::from_response(request_context(404, None), None, body),
+            YahooError::SymbolNotFound { symbol, .. } if symbol == "XXXX"
+        ));
+        let body = r#"{"chart":{"result":null,"error":{"code":"Unprocessable Entity","description":"1m data not available for startTime=1 and endTime=2. The requested range must be within the last 30 days."}}}"#;
+        assert!(matches!(
+            YahooError::from_response(request_context(422, Some("AAPL")), None, body),
+            YahooError::InvalidRange { .. }
+        ));
+        assert!(matches!(
+            YahooError::from_response(request_context(429, None), Some(Duration::from_secs(30)), "Too Many Requests"),
+            YahooError::RateLimited { retry_after: Some(d), .. } if d.as_secs() == 30
+        ));
+        let body = r#"{"finance":{"result":null,"error":{"code":"Unauthorized","description":"Invalid Crumb"}}}"#;
+        assert!(matches!(
+            YahooError::from_response(request_context(401, None), None, body),
+            YahooError::Unauthorized { .. }
+        ));
+        assert!(matches!(
+            YahooError::from_response(request_context(502, None), None, "Bad Gateway"),
+            YahooError::ServerError { status: 502, context } if context.snippet.as_deref() == Some("Bad Gateway")
+        ));
+        let page = "<html>".repeat(1000);
+        assert!(matches!(
+            YahooError::from_response(request_context(503, None), None, &page),
+            YahooError::ServerError { context, .. }
+                if context.snippet.as_ref().unwrap().chars().count() == SNIPPET_LENGTH
+        ));
+        assert!(matches!(
+            YahooError::from_response(request_context(404, None), None, ""),
+            YahooError::FetchFailed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_error_source_and_context() {
+        let source = serde_json::from_str::<u32>("\"x\"").unwrap_err();
+        let error = YahooError::from(source);
+        assert!(error.source().is_some());
+        assert_eq!(error.context().unwrap().url, None);
+
+        let long_body = "x".repeat(2 * SNIPPET_LENGTH);
+        let error = error.with_context(request_context(200, Some("XXXX")).with_body(long_body.as_bytes()));
+        let context = error.context().unwrap();
+        assert_eq!(context.symbol.as_deref(), Some("XXXX"));
+        assert_eq!(context.snippet.as_ref().unwrap().len(), SNIPPET_LENGTH);
+    }
+
+    #[test]
+    fn test_empty_data_set_context() {
+        let body = r#"{"quoteSummary":{"result":null,"error":{"code":"Not Found","description":"No fundamentals data found for any of the summaryTypes=esgScores"}}}"#;
+        let error = YahooError::from_response(request_context(404, Some("XXXX")), None, body);
+        assert!(matches!(&error, YahooError::EmptyDataSet { .. }));
+        assert_eq!(error.context().unwrap().status, Some(404));
+
+        let error = YahooError::empty_data_set().with_context(request_context(200, Some("XXXX")));
+        assert_eq!(error.context().unwrap().symbol.as_deref(), Some("XXXX"));
+        assert!(error.to_string().ends_with("status 200)"));
+    }
+}